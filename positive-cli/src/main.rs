@@ -0,0 +1,294 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 18/10/26
+******************************************************************************/
+
+//! `positive`: validate, normalize and compute on decimal data with the
+//! `positive` crate.
+//!
+//! * `positive check prices.csv --col price` reports every field of the
+//!   named columns that is not a valid `Positive`, and every malformed
+//!   record.
+//! * `positive fmt --dp 4 --grouping 1234.5` reformats values given as
+//!   arguments or, one per line, on standard input.
+//! * `positive grid --from 90 --to 110 --step 2.5` prints a price ladder.
+//! * `positive calc "100 * 1.05 / 3"` evaluates a `positive::expr` formula
+//!   with checked arithmetic.
+//!
+//! Every command exits with a non-zero status when a value is rejected.
+
+use clap::{Args, Parser, Subcommand};
+use positive::Positive;
+use positive::csv::{ParseOptions, PositiveReader};
+use positive::expr::Formula;
+use std::fs::File;
+use std::io::{self, BufRead, BufWriter, Read, Write};
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+#[derive(Parser)]
+#[command(name = "positive", version, about)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Report the fields of CSV columns that are not valid positive values
+    /// and the malformed records
+    Check(CheckArgs),
+    /// Reformat values at a fixed number of decimal places
+    Fmt(FmtArgs),
+    /// Print the grid from `--from` to `--to` in steps of `--step`
+    Grid(GridArgs),
+    /// Evaluate a formula with checked arithmetic
+    Calc(CalcArgs),
+}
+
+/// How input values are parsed, shared by `check` and `fmt`.
+#[derive(Args)]
+struct ParseArgs {
+    /// Separator between groups of three integer digits, such as `,`
+    #[arg(long, value_name = "CHAR")]
+    thousands_separator: Option<char>,
+    /// Separator between the integer and fractional parts
+    #[arg(long, value_name = "CHAR", default_value_t = '.')]
+    decimal_separator: char,
+    /// Accept scientific notation such as `1.5e3`
+    #[arg(long)]
+    allow_exponent: bool,
+    /// Read `-0` as zero instead of rejecting it
+    #[arg(long)]
+    allow_negative_zero: bool,
+}
+
+impl ParseArgs {
+    fn options(&self) -> ParseOptions {
+        ParseOptions {
+            thousands_separator: self.thousands_separator,
+            decimal_separator: self.decimal_separator,
+            allow_exponent: self.allow_exponent,
+            allow_negative_zero: self.allow_negative_zero,
+            ..ParseOptions::STRICT
+        }
+    }
+}
+
+#[derive(Args)]
+struct CheckArgs {
+    /// CSV file with a header row, or `-` for standard input
+    file: PathBuf,
+    /// Column to validate; repeat for several columns
+    #[arg(long = "col", value_name = "NAME", required = true)]
+    columns: Vec<String>,
+    #[command(flatten)]
+    parse: ParseArgs,
+}
+
+#[derive(Args)]
+struct FmtArgs {
+    /// Values to format; read one per line from standard input if omitted
+    #[arg(allow_negative_numbers = true)]
+    values: Vec<String>,
+    /// Decimal places of the output
+    #[arg(long, value_name = "N")]
+    dp: Option<u32>,
+    /// Separate groups of three integer digits with `,`
+    #[arg(long)]
+    grouping: bool,
+    #[command(flatten)]
+    parse: ParseArgs,
+}
+
+#[derive(Args)]
+struct GridArgs {
+    /// First value of the grid
+    #[arg(long)]
+    from: Positive,
+    /// Last value of the grid, included when it lies on a step
+    #[arg(long)]
+    to: Positive,
+    /// Distance between consecutive values
+    #[arg(long)]
+    step: Positive,
+    /// Decimal places of the output
+    #[arg(long, value_name = "N")]
+    dp: Option<u32>,
+}
+
+#[derive(Args)]
+struct CalcArgs {
+    /// Formula using numbers, `+`, `-`, `*`, `/`, parentheses and the
+    /// functions `min`, `max`, `sqrt`, `ln`, `round_to` and `clamp`
+    #[arg(required = true, num_args = 1.., allow_hyphen_values = true)]
+    expression: Vec<String>,
+    /// Decimal places of the output
+    #[arg(long, value_name = "N")]
+    dp: Option<u32>,
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let result = match cli.command {
+        Command::Check(args) => check(&args),
+        Command::Fmt(args) => fmt(&args),
+        Command::Grid(args) => grid(&args),
+        Command::Calc(args) => calc(&args),
+    };
+    match result {
+        Ok(code) => code,
+        Err(err) => {
+            eprintln!("error: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+type CommandResult = Result<ExitCode, Box<dyn std::error::Error>>;
+
+fn check(args: &CheckArgs) -> CommandResult {
+    let input: Box<dyn Read> = if args.file.as_os_str() == "-" {
+        Box::new(io::stdin().lock())
+    } else {
+        Box::new(
+            File::open(&args.file)
+                .map_err(|err| format!("cannot open {}: {err}", args.file.display()))?,
+        )
+    };
+    let columns: Vec<&str> = args.columns.iter().map(String::as_str).collect();
+    let mut reader = PositiveReader::from_reader(input, &columns, args.parse.options())?;
+    let mut valid = 0usize;
+    // Invalid fields and malformed records are kept in `errors()`; only
+    // I/O errors end the scan.
+    for row in reader.by_ref() {
+        row?;
+        valid += 1;
+    }
+
+    let name = args.file.display();
+    let mut stdout = BufWriter::new(io::stdout().lock());
+    for error in reader.errors() {
+        writeln!(stdout, "{name}: {error}")?;
+    }
+    stdout.flush()?;
+    let invalid = reader.errors().len();
+    eprintln!("{name}: {valid} valid records, {invalid} errors");
+    Ok(if invalid == 0 {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    })
+}
+
+fn fmt(args: &FmtArgs) -> CommandResult {
+    let options = args.parse.options();
+    let mut stdout = BufWriter::new(io::stdout().lock());
+    let mut failed = false;
+    let mut emit = |line: usize, text: &str| -> io::Result<()> {
+        match options.parse(text) {
+            Ok(value) => {
+                let formatted = format_value(value, args.dp);
+                let formatted = if args.grouping {
+                    group_thousands(&formatted)
+                } else {
+                    formatted
+                };
+                writeln!(stdout, "{formatted}")
+            }
+            Err(err) => {
+                failed = true;
+                eprintln!("error: line {line}: {err}");
+                Ok(())
+            }
+        }
+    };
+    if args.values.is_empty() {
+        for (index, line) in io::stdin().lock().lines().enumerate() {
+            let line = line?;
+            if !line.trim().is_empty() {
+                emit(index + 1, &line)?;
+            }
+        }
+    } else {
+        for (index, value) in args.values.iter().enumerate() {
+            emit(index + 1, value)?;
+        }
+    }
+    stdout.flush()?;
+    Ok(if failed {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    })
+}
+
+fn grid(args: &GridArgs) -> CommandResult {
+    let mut stdout = BufWriter::new(io::stdout().lock());
+    for value in Positive::range_inclusive(args.from, args.to, args.step)? {
+        writeln!(stdout, "{}", format_value(value, args.dp))?;
+    }
+    stdout.flush()?;
+    Ok(ExitCode::SUCCESS)
+}
+
+fn calc(args: &CalcArgs) -> CommandResult {
+    let source = args.expression.join(" ");
+    let result = Formula::parse(&source).and_then(|formula| formula.eval(&|_: &str| None));
+    match result {
+        Ok(value) => {
+            println!("{}", format_value(value, args.dp));
+            Ok(ExitCode::SUCCESS)
+        }
+        Err(err) => {
+            let start = source[..err.span.start].chars().count();
+            let width = source[err.span.clone()].chars().count().max(1);
+            eprintln!("error: {err}");
+            eprintln!("  {source}");
+            eprintln!("  {}{}", " ".repeat(start), "^".repeat(width));
+            Ok(ExitCode::FAILURE)
+        }
+    }
+}
+
+/// Formats `value` at `dp` decimal places, or as is without `dp`.
+fn format_value(value: Positive, dp: Option<u32>) -> String {
+    match dp {
+        Some(dp) => value.format_fixed_places(dp),
+        None => value.to_string(),
+    }
+}
+
+/// Inserts `,` between groups of three digits of the integer part.
+fn group_thousands(text: &str) -> String {
+    let (integer, fraction) = text.split_at(text.find('.').unwrap_or(text.len()));
+    let mut grouped = String::with_capacity(text.len() + integer.len() / 3);
+    for (index, digit) in integer.chars().enumerate() {
+        if index > 0 && (integer.len() - index) % 3 == 0 {
+            grouped.push(',');
+        }
+        grouped.push(digit);
+    }
+    grouped.push_str(fraction);
+    grouped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::CommandFactory;
+
+    #[test]
+    fn test_cli_definition() {
+        Cli::command().debug_assert();
+    }
+
+    #[test]
+    fn test_group_thousands() {
+        assert_eq!(group_thousands("0.5"), "0.5");
+        assert_eq!(group_thousands("999"), "999");
+        assert_eq!(group_thousands("1000"), "1,000");
+        assert_eq!(group_thousands("1234567.8900"), "1,234,567.8900");
+    }
+}
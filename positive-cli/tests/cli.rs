@@ -0,0 +1,145 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 18/10/26
+******************************************************************************/
+
+use std::io::Write;
+use std::process::{Command, Output, Stdio};
+
+fn run(args: &[&str], stdin: &str) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_positive"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(stdin.as_bytes())
+        .unwrap();
+    child.wait_with_output().unwrap()
+}
+
+fn stdout(output: &Output) -> String {
+    String::from_utf8(output.stdout.clone()).unwrap()
+}
+
+fn stderr(output: &Output) -> String {
+    String::from_utf8(output.stderr.clone()).unwrap()
+}
+
+#[test]
+fn test_check_reports_violations() {
+    let data = "sku,price,qty\na,10.5,1\nb,-2,1\nc,3,abc\n";
+    let output = run(&["check", "-", "--col", "price", "--col", "qty"], data);
+    assert!(!output.status.success());
+    let report = stdout(&output);
+    let lines: Vec<&str> = report.lines().collect();
+    assert_eq!(lines.len(), 2);
+    assert!(lines[0].starts_with("-: line 3, column `price`:"));
+    assert!(lines[1].starts_with("-: line 4, column `qty`:"));
+    assert!(stderr(&output).contains("1 valid records, 2 errors"));
+
+    let output = run(
+        &["check", "-", "--col", "price"],
+        "sku,price\na,-1\nb\nc,-2\n",
+    );
+    assert!(!output.status.success());
+    let report = stdout(&output);
+    let lines: Vec<&str> = report.lines().collect();
+    assert_eq!(lines.len(), 3);
+    assert!(lines[0].starts_with("-: line 2, column `price`:"));
+    assert!(lines[1].starts_with("-: line 3: "));
+    assert!(lines[2].starts_with("-: line 4, column `price`:"));
+    assert!(stderr(&output).contains("0 valid records, 3 errors"));
+
+    let output = run(&["check", "-", "--col", "price"], "price\n1\n2.5\n");
+    assert!(output.status.success());
+    assert!(stdout(&output).is_empty());
+
+    let output = run(&["check", "-", "--col", "missing"], "price\n1\n");
+    assert!(!output.status.success());
+    assert!(stderr(&output).starts_with("error: "));
+}
+
+#[test]
+fn test_fmt() {
+    let output = run(
+        &["fmt", "--dp", "4", "--grouping"],
+        "1234567.5\n\n0.12345\n",
+    );
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), "1,234,567.5000\n0.1234\n");
+
+    let output = run(&["fmt", "--thousands-separator", ",", "1,000", "-1"], "");
+    assert!(!output.status.success());
+    assert_eq!(stdout(&output), "1000\n");
+    assert!(stderr(&output).starts_with("error: line 2: "));
+}
+
+#[test]
+fn test_grid() {
+    let output = run(
+        &["grid", "--from", "90", "--to", "100", "--step", "2.5"],
+        "",
+    );
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), "90\n92.5\n95\n97.5\n100\n");
+
+    let output = run(
+        &[
+            "grid", "--from", "1", "--to", "2", "--step", "0.5", "--dp", "2",
+        ],
+        "",
+    );
+    assert_eq!(stdout(&output), "1.00\n1.50\n2.00\n");
+
+    let output = run(&["grid", "--from", "1", "--to", "2", "--step", "0"], "");
+    assert!(!output.status.success());
+    assert!(stderr(&output).starts_with("error: "));
+
+    let output = run(
+        &[
+            "grid",
+            "--from",
+            "0.00000000000000000001",
+            "--to",
+            "100000000000000000000",
+            "--step",
+            "0.00000000000000000001",
+        ],
+        "",
+    );
+    assert!(!output.status.success());
+    assert!(stderr(&output).starts_with("error: "));
+}
+
+#[test]
+fn test_calc() {
+    let output = run(&["calc", "100 * 1.05 / 3"], "");
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), "35\n");
+
+    let output = run(&["calc", "--dp", "2", "10", "/", "3"], "");
+    assert_eq!(stdout(&output), "3.33\n");
+
+    let output = run(&["calc", "max(100 * 0.0004, 1.5)"], "");
+    assert_eq!(stdout(&output), "1.5\n");
+
+    let output = run(&["calc", "1 + (1 - 2)"], "");
+    assert!(!output.status.success());
+    let message = stderr(&output);
+    assert!(message.starts_with("error: Value -1 is out of bounds"));
+    assert!(message.ends_with(" at 4..11\n  1 + (1 - 2)\n      ^^^^^^^\n"));
+
+    let output = run(&["calc", "(1 + 2"], "");
+    assert!(!output.status.success());
+    assert_eq!(
+        stderr(&output),
+        "error: expected `)` at 6..6\n  (1 + 2\n        ^\n"
+    );
+}
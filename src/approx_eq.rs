@@ -0,0 +1,158 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 18/10/26
+******************************************************************************/
+
+//! `approx` support for optional values and collections of `Positive`.
+//!
+//! `Positive` itself implements [`AbsDiffEq`], [`RelativeEq`] and
+//! [`UlpsEq`], and `approx` lifts those to `[Positive]` slices. The orphan
+//! rule prevents this crate from implementing the traits directly on
+//! `Option<Positive>`, `Vec<Positive>` or `[Positive; N]`, so [`Approx`]
+//! wraps them instead.
+//!
+//! ```rust
+//! use approx::{assert_abs_diff_eq, assert_relative_eq, assert_ulps_eq};
+//! use positive::{Approx, Positive};
+//! use rust_decimal_macros::dec;
+//!
+//! let p = |d| Positive::new_decimal(d).unwrap();
+//! let portfolio = vec![p(dec!(1.00)), p(dec!(2.50))];
+//! let expected = vec![p(dec!(1.01)), p(dec!(2.50))];
+//!
+//! assert_abs_diff_eq!(Approx(portfolio.clone()), Approx(expected.clone()), epsilon = dec!(0.01));
+//! assert_ulps_eq!(Approx(portfolio), Approx(expected), max_ulps = 1);
+//! assert_relative_eq!(Approx(Some(p(dec!(100)))), Approx(Some(p(dec!(100.5)))), max_relative = dec!(0.01));
+//! ```
+
+use crate::constants::{EPSILON, EPSILON_CMP};
+use crate::positive::Positive;
+use alloc::vec::Vec;
+use approx::{AbsDiffEq, RelativeEq, UlpsEq};
+use rust_decimal::Decimal;
+
+/// Wrapper implementing the `approx` traits for `Option<Positive>`,
+/// `Vec<Positive>`, `[Positive; N]` and `&[Positive]`.
+///
+/// Two `None`s are equal and `Some` never equals `None`. Collections are
+/// equal when they have the same length and every pair of elements is.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Approx<T>(pub T);
+
+fn slices_eq(a: &[Positive], b: &[Positive], eq: impl Fn(&Positive, &Positive) -> bool) -> bool {
+    a.len() == b.len() && a.iter().zip(b).all(|(x, y)| eq(x, y))
+}
+
+fn options_eq(
+    a: &Option<Positive>,
+    b: &Option<Positive>,
+    eq: impl Fn(&Positive, &Positive) -> bool,
+) -> bool {
+    match (a, b) {
+        (Some(x), Some(y)) => eq(x, y),
+        (None, None) => true,
+        _ => false,
+    }
+}
+
+macro_rules! impl_approx_for_wrapper {
+    ([$($generics:tt)*], $inner:ty, $cmp:ident $(, $index:tt)?) => {
+        impl<$($generics)*> AbsDiffEq for Approx<$inner> {
+            type Epsilon = Decimal;
+
+            fn default_epsilon() -> Self::Epsilon {
+                EPSILON
+            }
+
+            fn abs_diff_eq(&self, other: &Self, epsilon: Self::Epsilon) -> bool {
+                $cmp(&self.0$($index)?, &other.0$($index)?, |x, y| x.abs_diff_eq(y, epsilon))
+            }
+        }
+
+        impl<$($generics)*> RelativeEq for Approx<$inner> {
+            fn default_max_relative() -> Self::Epsilon {
+                EPSILON_CMP
+            }
+
+            fn relative_eq(
+                &self,
+                other: &Self,
+                epsilon: Self::Epsilon,
+                max_relative: Self::Epsilon,
+            ) -> bool {
+                $cmp(&self.0$($index)?, &other.0$($index)?, |x, y| {
+                    x.relative_eq(y, epsilon, max_relative)
+                })
+            }
+        }
+
+        impl<$($generics)*> UlpsEq for Approx<$inner> {
+            fn default_max_ulps() -> u32 {
+                Positive::default_max_ulps()
+            }
+
+            fn ulps_eq(&self, other: &Self, epsilon: Self::Epsilon, max_ulps: u32) -> bool {
+                $cmp(&self.0$($index)?, &other.0$($index)?, |x, y| {
+                    x.ulps_eq(y, epsilon, max_ulps)
+                })
+            }
+        }
+    };
+}
+
+impl_approx_for_wrapper!([], Option<Positive>, options_eq);
+impl_approx_for_wrapper!([], Vec<Positive>, slices_eq, [..]);
+impl_approx_for_wrapper!([const N: usize], [Positive; N], slices_eq, [..]);
+impl_approx_for_wrapper!(['a], &'a [Positive], slices_eq, [..]);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pos_or_panic;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_positive_ulps_eq() {
+        // Units in the last place follow the scale, so these keep `dec!`.
+        let one = Positive::new_decimal(dec!(1.00)).unwrap();
+        let near = Positive::new_decimal(dec!(1.04)).unwrap();
+        let far = Positive::new_decimal(dec!(1.05)).unwrap();
+        assert!(one.ulps_eq(&near, Decimal::ZERO, 4));
+        assert!(!one.ulps_eq(&far, Decimal::ZERO, 4));
+        assert!(one.ulps_eq(&far, dec!(0.05), 0));
+        approx::assert_ulps_eq!(pos_or_panic!(2.5), pos_or_panic!(2.5));
+    }
+
+    #[test]
+    fn test_option() {
+        let eps = dec!(0.1);
+        let one = Approx(Some(pos_or_panic!(1.0)));
+        assert!(one.abs_diff_eq(&Approx(Some(pos_or_panic!(1.05))), eps));
+        assert!(Approx(None::<Positive>).abs_diff_eq(&Approx(None), eps));
+        assert!(!one.abs_diff_eq(&Approx(None), eps));
+        assert!(!Approx(None).ulps_eq(&one, eps, 4));
+    }
+
+    #[test]
+    fn test_collections() {
+        let one = Positive::new_decimal(dec!(1.0)).unwrap();
+        let near = Positive::new_decimal(dec!(1.1)).unwrap();
+        let a = [one, pos_or_panic!(2.0)];
+        let b = [near, pos_or_panic!(2.0)];
+        assert!(Approx(a).relative_eq(&Approx(b), Decimal::ZERO, dec!(0.1)));
+        assert!(!Approx(a).relative_eq(&Approx(b), Decimal::ZERO, dec!(0.01)));
+        assert!(Approx(a.to_vec()).ulps_eq(&Approx(b.to_vec()), Decimal::ZERO, 1));
+        assert!(Approx(&a[..]).abs_diff_eq(&Approx(&b[..]), dec!(0.1)));
+        assert!(!Approx(&a[..]).abs_diff_eq(&Approx(&b[..1]), dec!(1)));
+        approx::assert_abs_diff_eq!(a[..], b[..], epsilon = dec!(0.1));
+    }
+
+    #[test]
+    fn test_ulp_and_epsilon() {
+        assert_eq!(pos_or_panic!(1.25).ulp(), pos_or_panic!(0.01));
+        assert_eq!(pos_or_panic!(100.0).ulp(), Positive::ONE);
+        assert_eq!(Positive::epsilon(3).unwrap(), pos_or_panic!(0.001));
+        assert!(Positive::epsilon(29).is_err());
+    }
+}
@@ -0,0 +1,239 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 18/10/26
+******************************************************************************/
+
+//! Zero-copy `rkyv` archives of `Positive`.
+//!
+//! A `Positive` archives to an [`ArchivedPositive`]: the 16-byte layout of
+//! [`Decimal::serialize`] with an alignment of one, so it can be read in
+//! place from a memory-mapped buffer on any platform. Validated access
+//! (`rkyv::access`, `rkyv::from_bytes`) runs `CheckBytes`, which rejects
+//! unknown flag bits, scales above 28 and values that break the
+//! positivity invariant.
+//!
+//! ```rust
+//! use positive::{ArchivedPositive, Positive, pos_or_panic};
+//! use rkyv::rancor::Error;
+//!
+//! let prices = vec![pos_or_panic!(101.25), pos_or_panic!(99.5)];
+//! let bytes = rkyv::to_bytes::<Error>(&prices).unwrap();
+//!
+//! let archived = rkyv::access::<rkyv::Archived<Vec<Positive>>, Error>(&bytes).unwrap();
+//! assert_eq!(archived[0].to_positive(), pos_or_panic!(101.25));
+//! assert!(archived[1] < archived[0]);
+//! assert_eq!(archived[1], pos_or_panic!(99.5));
+//! ```
+
+use crate::positive::Positive;
+use core::cmp::Ordering;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use rkyv::bytecheck::CheckBytes;
+use rkyv::rancor::{Fallible, Source, fail};
+use rkyv::traits::NoUndef;
+use rkyv::{Archive, Deserialize, Place, Portable, Serialize};
+use rust_decimal::Decimal;
+
+/// The archived form of [`Positive`].
+///
+/// Compares, orders and hashes by numeric value, like `Positive`, so
+/// `1.0` and `1.00` are equal.
+#[derive(Clone, Copy, Portable)]
+#[repr(transparent)]
+pub struct ArchivedPositive {
+    bytes: [u8; 16],
+}
+
+// SAFETY: `ArchivedPositive` is a plain byte array with no padding.
+unsafe impl NoUndef for ArchivedPositive {}
+
+impl ArchivedPositive {
+    /// Returns the archived value as a `Decimal`.
+    #[must_use]
+    #[inline]
+    pub fn to_dec(&self) -> Decimal {
+        Decimal::deserialize(self.bytes)
+    }
+
+    /// Returns the archived value as a `Positive`.
+    #[must_use]
+    #[inline]
+    pub fn to_positive(&self) -> Positive {
+        // SAFETY: archives are produced from valid `Positive`s and checked
+        // by `CheckBytes` on validated access; callers of the unchecked
+        // `rkyv` accessors vouch for the bytes themselves.
+        unsafe { Positive::new_unchecked(self.to_dec()) }
+    }
+}
+
+impl fmt::Debug for ArchivedPositive {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("ArchivedPositive")
+            .field(&self.to_dec())
+            .finish()
+    }
+}
+
+impl fmt::Display for ArchivedPositive {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.to_positive(), f)
+    }
+}
+
+impl PartialEq for ArchivedPositive {
+    fn eq(&self, other: &Self) -> bool {
+        self.to_dec() == other.to_dec()
+    }
+}
+
+impl Eq for ArchivedPositive {}
+
+impl PartialOrd for ArchivedPositive {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for ArchivedPositive {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.to_dec().cmp(&other.to_dec())
+    }
+}
+
+impl Hash for ArchivedPositive {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.to_positive().hash(state);
+    }
+}
+
+impl PartialEq<Positive> for ArchivedPositive {
+    fn eq(&self, other: &Positive) -> bool {
+        self.to_dec() == other.to_dec()
+    }
+}
+
+impl PartialEq<ArchivedPositive> for Positive {
+    fn eq(&self, other: &ArchivedPositive) -> bool {
+        self.to_dec() == other.to_dec()
+    }
+}
+
+impl PartialOrd<Positive> for ArchivedPositive {
+    fn partial_cmp(&self, other: &Positive) -> Option<Ordering> {
+        Some(self.to_dec().cmp(&other.to_dec()))
+    }
+}
+
+impl PartialOrd<ArchivedPositive> for Positive {
+    fn partial_cmp(&self, other: &ArchivedPositive) -> Option<Ordering> {
+        Some(self.to_dec().cmp(&other.to_dec()))
+    }
+}
+
+impl Archive for Positive {
+    type Archived = ArchivedPositive;
+    type Resolver = ();
+
+    fn resolve(&self, _resolver: Self::Resolver, out: Place<Self::Archived>) {
+        out.write(ArchivedPositive {
+            bytes: self.to_dec().serialize(),
+        });
+    }
+}
+
+impl<S: Fallible + ?Sized> Serialize<S> for Positive {
+    fn serialize(&self, _serializer: &mut S) -> Result<Self::Resolver, S::Error> {
+        Ok(())
+    }
+}
+
+impl<D: Fallible + ?Sized> Deserialize<Positive, D> for ArchivedPositive {
+    fn deserialize(&self, _deserializer: &mut D) -> Result<Positive, D::Error> {
+        Ok(self.to_positive())
+    }
+}
+
+// SAFETY: every bit pattern of the byte array is readable; `check_bytes`
+// only succeeds for bytes that decode to a valid `Positive`.
+unsafe impl<C> CheckBytes<C> for ArchivedPositive
+where
+    C: Fallible + ?Sized,
+    C::Error: Source,
+{
+    unsafe fn check_bytes(value: *const Self, _context: &mut C) -> Result<(), C::Error> {
+        // SAFETY: the caller guarantees `value` is aligned and points to
+        // 16 readable bytes.
+        let bytes = unsafe { (*value).bytes };
+        match crate::binary::from_bytes(bytes) {
+            Ok(_) => Ok(()),
+            Err(err) => fail!(err),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pos_or_panic;
+    use alloc::vec::Vec;
+    use rkyv::rancor::Error;
+    use rust_decimal_macros::dec;
+
+    fn archive(value: &Positive) -> rkyv::util::AlignedVec {
+        rkyv::to_bytes::<Error>(value).unwrap()
+    }
+
+    #[test]
+    fn test_round_trip_keeps_scale() {
+        let value = Positive::new_decimal(dec!(123.4500)).unwrap();
+        let bytes = archive(&value);
+        assert_eq!(bytes.len(), 16);
+        let back = rkyv::from_bytes::<Positive, Error>(&bytes).unwrap();
+        assert_eq!(back, value);
+        assert_eq!(back.to_dec().scale(), 4);
+    }
+
+    #[test]
+    fn test_archived_compares_in_place() {
+        let values = vec![pos_or_panic!(1.0), pos_or_panic!(2.5), Positive::INFINITY];
+        let bytes = rkyv::to_bytes::<Error>(&values).unwrap();
+        let archived = rkyv::access::<rkyv::Archived<Vec<Positive>>, Error>(&bytes).unwrap();
+        assert!(archived[0] < archived[1]);
+        assert!(archived[1] < pos_or_panic!(3.0));
+        assert_eq!(archived[2], Positive::INFINITY);
+        assert_eq!(archived[1].to_string(), "2.5");
+
+        let one = Positive::new_decimal(dec!(1.00)).unwrap();
+        let other = archive(&one);
+        let other = rkyv::access::<ArchivedPositive, Error>(&other).unwrap();
+        assert_eq!(&archived[0], other);
+    }
+
+    #[test]
+    fn test_check_bytes_rejects_negative() {
+        let bytes = rkyv::to_bytes::<Error>(&dec!(-1.5).serialize()).unwrap();
+        assert!(rkyv::access::<ArchivedPositive, Error>(&bytes).is_err());
+    }
+
+    #[test]
+    fn test_check_bytes_rejects_malformed() {
+        let mut raw = dec!(1.5).serialize();
+        raw[2] = 29;
+        let bytes = rkyv::to_bytes::<Error>(&raw).unwrap();
+        assert!(rkyv::access::<ArchivedPositive, Error>(&bytes).is_err());
+
+        let mut raw = dec!(1.5).serialize();
+        raw[0] = 1;
+        let bytes = rkyv::to_bytes::<Error>(&raw).unwrap();
+        assert!(rkyv::access::<ArchivedPositive, Error>(&bytes).is_err());
+    }
+
+    #[cfg(feature = "non-zero")]
+    #[test]
+    fn test_check_bytes_rejects_zero_under_non_zero() {
+        let bytes = rkyv::to_bytes::<Error>(&Decimal::ZERO.serialize()).unwrap();
+        assert!(rkyv::access::<ArchivedPositive, Error>(&bytes).is_err());
+    }
+}
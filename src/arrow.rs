@@ -0,0 +1,459 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 18/10/26
+******************************************************************************/
+
+//! Apache Arrow columns of `Positive` values.
+//!
+//! [`PositiveArray`] wraps an Arrow `Decimal128Array` whose values are known
+//! to be valid `Positive`s: no nulls, a scale between 0 and 28, and every
+//! value non-negative (positive under the `non-zero` feature) and within
+//! the `Decimal` range. It is built from a slice with an explicit
+//! precision and scale, failing instead of rounding when a value does not
+//! fit, or validated from an existing array, for example one read from
+//! Parquet.
+//!
+//! Aggregates and quantization work directly on the `i128` buffer: `sum`,
+//! `min` and `max` allocate nothing, and `quantize` allocates only the
+//! output buffer.
+//!
+//! ```rust
+//! use positive::{PositiveArray, pos_or_panic};
+//! use rust_decimal::RoundingStrategy;
+//!
+//! let prices = [pos_or_panic!(101.25), pos_or_panic!(99.5), pos_or_panic!(100.125)];
+//! let column = PositiveArray::from_positives(&prices, 18, 4).unwrap();
+//!
+//! assert_eq!(column.sum().unwrap(), pos_or_panic!(300.875));
+//! assert_eq!(column.max(), Some(pos_or_panic!(101.25)));
+//!
+//! let ticks = column.quantize(2, RoundingStrategy::MidpointNearestEven).unwrap();
+//! assert_eq!(ticks.value(2), pos_or_panic!(100.12));
+//!
+//! let array: arrow_array::Decimal128Array = ticks.into();
+//! assert_eq!(array.value(0), 10125);
+//! ```
+
+use crate::binary::{MAX_MANTISSA, from_parts};
+use crate::error::{Operation, PositiveError, PositiveResult};
+use crate::positive::Positive;
+use arrow_array::types::Decimal128Type;
+use arrow_array::{Array, Decimal128Array};
+use rust_decimal::{Decimal, RoundingStrategy};
+
+/// Largest precision of an Arrow `Decimal128`.
+const MAX_PRECISION: u8 = 38;
+
+/// A validated Arrow `Decimal128Array` of `Positive` values.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PositiveArray {
+    array: Decimal128Array,
+}
+
+impl PositiveArray {
+    /// Stores `values` with the given Arrow `precision` and `scale`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `InvalidPrecision` error if `scale` is not between 0 and
+    /// 28, or `precision` is not between 1 and 38 or is below `scale`, and
+    /// a `ConversionError` if a value has more decimal places than `scale`,
+    /// more digits than `precision`, or more digits at `scale` than the
+    /// 96-bit `Decimal` mantissa holds.
+    pub fn from_positives(values: &[Positive], precision: u8, scale: i8) -> PositiveResult<Self> {
+        let target = check_scale(scale)?;
+        let limit = check_precision(precision, target)?;
+        let mut raw = Vec::with_capacity(values.len());
+        for (index, value) in values.iter().enumerate() {
+            let decimal = value.to_dec();
+            let mantissa = rescale(decimal.mantissa(), decimal.scale(), target)
+                .filter(|&mantissa| fits(mantissa, limit))
+                .ok_or_else(|| {
+                    PositiveError::conversion_error(
+                        "Positive",
+                        "Decimal128",
+                        &format!(
+                            "value {decimal} at index {index} does not fit \
+                             Decimal128({precision}, {scale})"
+                        ),
+                    )
+                })?;
+            raw.push(mantissa);
+        }
+        let array = Decimal128Array::from(raw)
+            .with_precision_and_scale(precision, scale)
+            .map_err(|err| {
+                PositiveError::conversion_error("Positive", "Decimal128", &err.to_string())
+            })?;
+        Ok(PositiveArray { array })
+    }
+
+    /// Validates an existing `Decimal128Array`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ConversionError` if the array contains nulls or a value
+    /// beyond the `Decimal` range, an `InvalidPrecision` error if its scale
+    /// is not between 0 and 28, and the invariant error of the first value
+    /// that is not a valid `Positive`.
+    pub fn try_new(array: Decimal128Array) -> PositiveResult<Self> {
+        if array.null_count() > 0 {
+            return Err(PositiveError::conversion_error(
+                "Decimal128",
+                "Positive",
+                "array contains nulls",
+            ));
+        }
+        let scale = check_scale(array.scale())?;
+        for &mantissa in array.values().iter() {
+            to_positive(mantissa, scale)?;
+        }
+        Ok(PositiveArray { array })
+    }
+
+    /// Returns the number of values.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.array.len()
+    }
+
+    /// Returns `true` if the array holds no values.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.array.is_empty()
+    }
+
+    /// Returns the Arrow precision of the column.
+    #[must_use]
+    pub fn precision(&self) -> u8 {
+        self.array.precision()
+    }
+
+    /// Returns the Arrow scale of the column.
+    #[must_use]
+    pub fn scale(&self) -> i8 {
+        self.array.scale()
+    }
+
+    /// Returns the value at `index`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    #[must_use]
+    pub fn value(&self, index: usize) -> Positive {
+        self.positive(self.array.value(index))
+    }
+
+    /// Iterates over the values.
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = Positive> + '_ {
+        self.array
+            .values()
+            .iter()
+            .map(|&mantissa| self.positive(mantissa))
+    }
+
+    /// Collects the values into a `Vec`.
+    #[must_use]
+    pub fn to_vec(&self) -> Vec<Positive> {
+        self.iter().collect()
+    }
+
+    /// Returns the underlying Arrow array.
+    #[must_use]
+    pub fn as_array(&self) -> &Decimal128Array {
+        &self.array
+    }
+
+    /// Sums the values, returning zero for an empty array.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `ArithmeticError` if the sum exceeds the `Decimal` range,
+    /// and an invariant error for an empty array under the `non-zero`
+    /// feature.
+    pub fn sum(&self) -> PositiveResult<Positive> {
+        let overflow = || PositiveError::arithmetic_error(Operation::Add, "overflow");
+        let total = self
+            .array
+            .values()
+            .iter()
+            .try_fold(0i128, |total, &mantissa| total.checked_add(mantissa))
+            .ok_or_else(overflow)?;
+        if total.unsigned_abs() > MAX_MANTISSA {
+            return Err(overflow());
+        }
+        to_positive(total, self.raw_scale())
+    }
+
+    /// Returns the smallest value, or `None` for an empty array.
+    #[must_use]
+    pub fn min(&self) -> Option<Positive> {
+        let mantissa = self.array.values().iter().copied().min()?;
+        Some(self.positive(mantissa))
+    }
+
+    /// Returns the largest value, or `None` for an empty array.
+    #[must_use]
+    pub fn max(&self) -> Option<Positive> {
+        let mantissa = self.array.values().iter().copied().max()?;
+        Some(self.positive(mantissa))
+    }
+
+    /// Rounds every value to `scale` decimal places with `strategy`,
+    /// keeping the precision.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `InvalidPrecision` error if `scale` is not between 0 and
+    /// 28 or exceeds the precision, a `ConversionError` if a value no
+    /// longer fits the precision or the `Decimal` mantissa, and an invariant error if a value rounds
+    /// to zero under the `non-zero` feature.
+    pub fn quantize(&self, scale: i8, strategy: RoundingStrategy) -> PositiveResult<Self> {
+        let target = check_scale(scale)?;
+        let precision = self.precision();
+        let limit = check_precision(precision, target)?;
+        let from = self.raw_scale();
+        let array = self
+            .array
+            .try_unary::<_, Decimal128Type, PositiveError>(|mantissa| {
+                let rounded = Decimal::from_i128_with_scale(mantissa, from)
+                    .round_dp_with_strategy(target, strategy);
+                let rounded = Positive::new_decimal(rounded)?.to_dec();
+                rescale(rounded.mantissa(), rounded.scale(), target)
+                    .filter(|&mantissa| fits(mantissa, limit))
+                    .ok_or_else(|| {
+                        PositiveError::conversion_error(
+                            "Positive",
+                            "Decimal128",
+                            &format!(
+                                "value {rounded} does not fit Decimal128({precision}, {scale})"
+                            ),
+                        )
+                    })
+            })?
+            .with_precision_and_scale(precision, scale)
+            .map_err(|err| {
+                PositiveError::conversion_error("Positive", "Decimal128", &err.to_string())
+            })?;
+        Ok(PositiveArray { array })
+    }
+
+    /// The scale as a `Decimal` scale; validated to be within `0..=28`.
+    fn raw_scale(&self) -> u32 {
+        self.array.scale() as u32
+    }
+
+    fn positive(&self, mantissa: i128) -> Positive {
+        let decimal = Decimal::from_i128_with_scale(mantissa, self.raw_scale());
+        // SAFETY: every value was validated when the array was built.
+        unsafe { Positive::new_unchecked(decimal) }
+    }
+}
+
+impl TryFrom<Decimal128Array> for PositiveArray {
+    type Error = PositiveError;
+
+    fn try_from(array: Decimal128Array) -> Result<Self, Self::Error> {
+        PositiveArray::try_new(array)
+    }
+}
+
+impl From<PositiveArray> for Decimal128Array {
+    fn from(array: PositiveArray) -> Self {
+        array.array
+    }
+}
+
+fn check_scale(scale: i8) -> PositiveResult<u32> {
+    if !(0..=Decimal::MAX_SCALE as i8).contains(&scale) {
+        return Err(PositiveError::invalid_precision(
+            i32::from(scale),
+            "scale must be between 0 and 28",
+        ));
+    }
+    Ok(scale as u32)
+}
+
+/// Returns `10^precision`, the exclusive bound of the stored mantissas.
+fn check_precision(precision: u8, scale: u32) -> PositiveResult<i128> {
+    if precision == 0 || precision > MAX_PRECISION || u32::from(precision) < scale {
+        return Err(PositiveError::invalid_precision(
+            i32::from(precision),
+            "precision must be between 1 and 38 and at least the scale",
+        ));
+    }
+    Ok(10i128.pow(u32::from(precision)))
+}
+
+/// Moves `mantissa` from scale `from` to scale `to`, or `None` if that
+/// would drop non-zero digits or overflow.
+fn rescale(mantissa: i128, from: u32, to: u32) -> Option<i128> {
+    if to >= from {
+        mantissa.checked_mul(10i128.pow(to - from))
+    } else {
+        let divisor = 10i128.pow(from - to);
+        (mantissa % divisor == 0).then_some(mantissa / divisor)
+    }
+}
+
+/// Returns whether a stored mantissa is below the precision bound `limit`
+/// and can still be read back as a `Decimal`.
+fn fits(mantissa: i128, limit: i128) -> bool {
+    mantissa < limit && mantissa.unsigned_abs() <= MAX_MANTISSA
+}
+
+fn to_positive(mantissa: i128, scale: u32) -> PositiveResult<Positive> {
+    from_parts(mantissa.unsigned_abs(), mantissa < 0, scale)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn positives(values: &[Decimal]) -> Vec<Positive> {
+        values
+            .iter()
+            .map(|&value| Positive::new_decimal(value).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let values = positives(&[dec!(1.5), dec!(0.0001), dec!(12345.678)]);
+        let column = PositiveArray::from_positives(&values, 20, 4).unwrap();
+        assert_eq!(column.as_array().values().as_ref(), &[15000, 1, 123456780]);
+        assert_eq!((column.precision(), column.scale()), (20, 4));
+        assert_eq!(column.to_vec(), values);
+
+        let back = PositiveArray::try_from(Decimal128Array::from(column.clone())).unwrap();
+        assert_eq!(back, column);
+    }
+
+    #[test]
+    fn test_export_rejects_loss() {
+        let values = positives(&[dec!(1.25), dec!(1.234)]);
+        let err = PositiveArray::from_positives(&values, 10, 2).unwrap_err();
+        assert_eq!(err.code(), "conversion");
+        assert!(err.to_string().contains("index 1"));
+
+        let values = positives(&[dec!(999), dec!(1000)]);
+        assert!(PositiveArray::from_positives(&values, 3, 0).is_err());
+        assert!(PositiveArray::from_positives(&values, 4, 0).is_ok());
+
+        assert_eq!(
+            PositiveArray::from_positives(&values, 10, 29)
+                .unwrap_err()
+                .code(),
+            "invalid_precision"
+        );
+        assert_eq!(
+            PositiveArray::from_positives(&values, 2, 3)
+                .unwrap_err()
+                .code(),
+            "invalid_precision"
+        );
+    }
+
+    #[test]
+    fn test_import_validates() {
+        let negative = Decimal128Array::from(vec![100, -1])
+            .with_precision_and_scale(10, 2)
+            .unwrap();
+        assert_eq!(
+            PositiveArray::try_new(negative).unwrap_err().code(),
+            "out_of_bounds"
+        );
+
+        let nulls = Decimal128Array::from(vec![Some(100), None]);
+        assert_eq!(
+            PositiveArray::try_new(nulls).unwrap_err().code(),
+            "conversion"
+        );
+
+        let negative_scale = Decimal128Array::from(vec![1])
+            .with_precision_and_scale(10, -2)
+            .unwrap();
+        assert!(PositiveArray::try_new(negative_scale).is_err());
+
+        let huge = Decimal128Array::from(vec![10i128.pow(30)])
+            .with_precision_and_scale(38, 0)
+            .unwrap();
+        assert_eq!(
+            PositiveArray::try_new(huge).unwrap_err().code(),
+            "conversion"
+        );
+    }
+
+    #[test]
+    fn test_aggregates() {
+        let values = positives(&[dec!(2.5), dec!(0.75), dec!(10)]);
+        let column = PositiveArray::from_positives(&values, 10, 2).unwrap();
+        assert_eq!(column.sum().unwrap().to_dec(), dec!(13.25));
+        assert_eq!(column.min().unwrap().to_dec(), dec!(0.75));
+        assert_eq!(column.max().unwrap().to_dec(), dec!(10));
+
+        let empty = PositiveArray::from_positives(&[], 10, 2).unwrap();
+        assert!(empty.is_empty());
+        assert_eq!(empty.min(), None);
+        assert_eq!(empty.max(), None);
+        assert_eq!(empty.sum().is_err(), cfg!(feature = "non-zero"));
+    }
+
+    #[test]
+    fn test_sum_overflow() {
+        let values = [Positive::INFINITY, Positive::INFINITY];
+        let column = PositiveArray::from_positives(&values, 38, 0).unwrap();
+        assert_eq!(column.sum().unwrap_err().code(), "arithmetic");
+    }
+
+    #[test]
+    fn test_quantize() {
+        let values = positives(&[dec!(1.125), dec!(1.135), dec!(2)]);
+        let column = PositiveArray::from_positives(&values, 10, 3).unwrap();
+
+        let even = column
+            .quantize(2, RoundingStrategy::MidpointNearestEven)
+            .unwrap();
+        assert_eq!(even.scale(), 2);
+        assert_eq!(even.as_array().values().as_ref(), &[112, 114, 200]);
+
+        let away = column
+            .quantize(2, RoundingStrategy::MidpointAwayFromZero)
+            .unwrap();
+        assert_eq!(away.as_array().values().as_ref(), &[113, 114, 200]);
+
+        let wider = column.quantize(5, RoundingStrategy::ToZero).unwrap();
+        assert_eq!(wider.value(0).to_dec(), dec!(1.125));
+        assert!(column.quantize(11, RoundingStrategy::ToZero).is_err());
+
+        let narrow = PositiveArray::from_positives(&values, 4, 3).unwrap();
+        let err = narrow.quantize(4, RoundingStrategy::ToZero).unwrap_err();
+        assert_eq!(err.code(), "conversion");
+
+        let large = PositiveArray::from_positives(&positives(&[dec!(1e9)]), 38, 0).unwrap();
+        let err = large.quantize(28, RoundingStrategy::ToZero).unwrap_err();
+        assert_eq!(err.code(), "conversion");
+    }
+
+    #[test]
+    fn test_mantissa_beyond_decimal_range() {
+        let values = positives(&[dec!(1e9)]);
+        let err = PositiveArray::from_positives(&values, 38, 28).unwrap_err();
+        assert_eq!(err.code(), "conversion");
+
+        let raw = Decimal128Array::from(vec![10i128.pow(37)])
+            .with_precision_and_scale(38, 28)
+            .unwrap();
+        assert_eq!(
+            PositiveArray::try_new(raw).unwrap_err().code(),
+            "conversion"
+        );
+
+        let column = PositiveArray::from_positives(&values, 38, 19).unwrap();
+        let back = PositiveArray::try_new(column.clone().into()).unwrap();
+        assert_eq!(back.max(), Some(values[0]));
+    }
+}
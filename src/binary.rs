@@ -0,0 +1,264 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 18/10/26
+******************************************************************************/
+
+//! Compact binary encodings of `Positive`.
+//!
+//! Human-readable serde formats keep the JSON number produced by the
+//! `Serialize` impl. Binary serde formats (those whose
+//! `is_human_readable()` is `false`, such as bincode and postcard) receive
+//! an exact `(mantissa, scale)` tuple instead: the unsigned 96-bit
+//! mantissa as a `u128` followed by the scale as a `u8`, which varint
+//! formats shrink to a few bytes for typical prices and quantities.
+//!
+//! With the `borsh` feature, `Positive` implements `BorshSerialize` and
+//! `BorshDeserialize` using the fixed 16-byte layout of
+//! [`Decimal::serialize`].
+//!
+//! Both decoders validate the scale, the mantissa and the positivity
+//! invariant, so a malformed or negative payload is rejected instead of
+//! producing an invalid `Positive`.
+//!
+//! ```rust
+//! use positive::pos_or_panic;
+//!
+//! let price = pos_or_panic!(101.25);
+//! let bytes = postcard::to_allocvec(&price).unwrap();
+//! assert_eq!(bytes, [0x8D, 0x4F, 2]);
+//! assert_eq!(postcard::from_bytes::<positive::Positive>(&bytes).unwrap(), price);
+//! ```
+
+use crate::error::{PositiveError, PositiveResult};
+use crate::positive::Positive;
+#[cfg(any(feature = "borsh", feature = "rkyv"))]
+use alloc::format;
+use rust_decimal::Decimal;
+
+/// Largest mantissa a `Decimal` can hold (2^96 - 1).
+pub(crate) const MAX_MANTISSA: u128 = (1 << 96) - 1;
+
+/// Sign bit of the `Decimal::serialize` flags word.
+#[cfg(any(feature = "borsh", feature = "rkyv"))]
+const SIGN_MASK: u32 = 0x8000_0000;
+
+/// Scale bits of the `Decimal::serialize` flags word.
+#[cfg(any(feature = "borsh", feature = "rkyv"))]
+const SCALE_MASK: u32 = 0x00FF_0000;
+
+/// Splits a value into its unsigned mantissa and scale.
+#[cfg(feature = "serde")]
+fn to_parts(value: Positive) -> (u128, u8) {
+    let decimal = value.to_dec();
+    // The scale of a `Decimal` never exceeds 28.
+    (decimal.mantissa().unsigned_abs(), decimal.scale() as u8)
+}
+
+/// Rebuilds a value from its parts, validating every component.
+pub(crate) fn from_parts(mantissa: u128, negative: bool, scale: u32) -> PositiveResult<Positive> {
+    if scale > Decimal::MAX_SCALE {
+        return Err(PositiveError::invalid_precision(
+            scale as i32,
+            "scale exceeds the maximum Decimal scale of 28",
+        ));
+    }
+    if mantissa > MAX_MANTISSA {
+        return Err(PositiveError::conversion_error(
+            "u128",
+            "Decimal",
+            "mantissa exceeds 96 bits",
+        ));
+    }
+    let mut decimal = Decimal::from_i128_with_scale(mantissa as i128, scale);
+    decimal.set_sign_negative(negative && mantissa != 0);
+    Positive::new_decimal(decimal)
+}
+
+/// Decodes the 16-byte layout of [`Decimal::serialize`].
+#[cfg(any(feature = "borsh", feature = "rkyv"))]
+pub(crate) fn from_bytes(bytes: [u8; 16]) -> PositiveResult<Positive> {
+    let word = |i: usize| u32::from_le_bytes([bytes[i], bytes[i + 1], bytes[i + 2], bytes[i + 3]]);
+    let flags = word(0);
+    if flags & !(SIGN_MASK | SCALE_MASK) != 0 {
+        return Err(PositiveError::conversion_error(
+            "bytes",
+            "Decimal",
+            &format!("invalid flags {flags:#010x}"),
+        ));
+    }
+    let mantissa = u128::from(word(4)) | u128::from(word(8)) << 32 | u128::from(word(12)) << 64;
+    from_parts(mantissa, flags & SIGN_MASK != 0, (flags & SCALE_MASK) >> 16)
+}
+
+#[cfg(feature = "serde")]
+pub(crate) fn serialize_compact<S>(value: &Positive, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    use serde::ser::SerializeTuple;
+
+    let (mantissa, scale) = to_parts(*value);
+    let mut tuple = serializer.serialize_tuple(2)?;
+    tuple.serialize_element(&mantissa)?;
+    tuple.serialize_element(&scale)?;
+    tuple.end()
+}
+
+#[cfg(feature = "serde")]
+pub(crate) fn deserialize_compact<'de, D>(deserializer: D) -> Result<Positive, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    use core::fmt;
+    use serde::de::{Error, SeqAccess, Visitor};
+
+    struct CompactVisitor;
+
+    impl<'de> Visitor<'de> for CompactVisitor {
+        type Value = Positive;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            formatter.write_str("a (mantissa, scale) pair")
+        }
+
+        fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+        where
+            A: SeqAccess<'de>,
+        {
+            let mantissa: u128 = seq
+                .next_element()?
+                .ok_or_else(|| Error::invalid_length(0, &self))?;
+            let scale: u8 = seq
+                .next_element()?
+                .ok_or_else(|| Error::invalid_length(1, &self))?;
+            from_parts(mantissa, false, u32::from(scale)).map_err(crate::de::custom)
+        }
+    }
+
+    deserializer.deserialize_tuple(2, CompactVisitor)
+}
+
+#[cfg(feature = "borsh")]
+impl borsh::BorshSerialize for Positive {
+    fn serialize<W: borsh::io::Write>(&self, writer: &mut W) -> borsh::io::Result<()> {
+        writer.write_all(&self.to_dec().serialize())
+    }
+}
+
+#[cfg(feature = "borsh")]
+impl borsh::BorshDeserialize for Positive {
+    fn deserialize_reader<R: borsh::io::Read>(reader: &mut R) -> borsh::io::Result<Self> {
+        use alloc::string::ToString;
+
+        let bytes = <[u8; 16]>::deserialize_reader(reader)?;
+        from_bytes(bytes).map_err(|err| {
+            borsh::io::Error::new(borsh::io::ErrorKind::InvalidData, err.to_string())
+        })
+    }
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+    use super::*;
+    use crate::pos_or_panic;
+    use alloc::vec::Vec;
+    use rust_decimal_macros::dec;
+
+    fn samples() -> Vec<Positive> {
+        let mut values = vec![
+            pos_or_panic!(1.0),
+            pos_or_panic!(101.25),
+            Positive::new_decimal(dec!(0.0000000000000000000000000001)).unwrap(),
+            Positive::new_decimal(dec!(123456789.123456789)).unwrap(),
+            Positive::INFINITY,
+        ];
+        // Zero is only valid without the `non-zero` feature.
+        values.extend(Positive::new_decimal(Decimal::ZERO).ok());
+        values
+    }
+
+    fn bincode_config() -> bincode::config::Configuration {
+        bincode::config::standard()
+    }
+
+    #[test]
+    fn test_bincode_round_trip_is_exact() {
+        for value in samples() {
+            let bytes = bincode::serde::encode_to_vec(value, bincode_config()).unwrap();
+            let (back, read): (Positive, usize) =
+                bincode::serde::decode_from_slice(&bytes, bincode_config()).unwrap();
+            assert_eq!(read, bytes.len());
+            assert_eq!(back.to_dec(), value.to_dec());
+            assert_eq!(back.to_dec().scale(), value.to_dec().scale());
+        }
+    }
+
+    #[test]
+    fn test_postcard_round_trip_is_exact() {
+        for value in samples() {
+            let bytes = postcard::to_allocvec(&value).unwrap();
+            assert!(bytes.len() <= 15);
+            let back: Positive = postcard::from_bytes(&bytes).unwrap();
+            assert_eq!(back.to_dec(), value.to_dec());
+            assert_eq!(back.to_dec().scale(), value.to_dec().scale());
+        }
+    }
+
+    #[test]
+    fn test_compact_is_smaller_than_f64() {
+        let bytes = postcard::to_allocvec(&pos_or_panic!(2.5)).unwrap();
+        assert_eq!(bytes, [25, 1]);
+    }
+
+    #[test]
+    fn test_compact_rejects_invalid_scale_and_mantissa() {
+        let bad_scale = postcard::to_allocvec(&(1u128, 29u8)).unwrap();
+        assert!(postcard::from_bytes::<Positive>(&bad_scale).is_err());
+
+        let bad_mantissa = postcard::to_allocvec(&(MAX_MANTISSA + 1, 0u8)).unwrap();
+        assert!(postcard::from_bytes::<Positive>(&bad_mantissa).is_err());
+    }
+
+    #[cfg(feature = "non-zero")]
+    #[test]
+    fn test_compact_rejects_zero_under_non_zero() {
+        let zero = postcard::to_allocvec(&(0u128, 0u8)).unwrap();
+        assert!(postcard::from_bytes::<Positive>(&zero).is_err());
+    }
+
+    #[test]
+    fn test_human_readable_format_is_unchanged() {
+        assert_eq!(serde_json::to_string(&pos_or_panic!(2.5)).unwrap(), "2.5");
+        assert_eq!(serde_json::to_string(&pos_or_panic!(42.0)).unwrap(), "42");
+    }
+
+    #[cfg(feature = "borsh")]
+    #[test]
+    fn test_borsh_round_trip() {
+        for value in samples() {
+            let bytes = borsh::to_vec(&value).unwrap();
+            assert_eq!(bytes.len(), 16);
+            let back: Positive = borsh::from_slice(&bytes).unwrap();
+            assert_eq!(back.to_dec(), value.to_dec());
+            assert_eq!(back.to_dec().scale(), value.to_dec().scale());
+        }
+    }
+
+    #[cfg(feature = "borsh")]
+    #[test]
+    fn test_borsh_rejects_negative_and_malformed() {
+        let negative = dec!(-1.5).serialize();
+        assert!(borsh::from_slice::<Positive>(&negative).is_err());
+
+        let mut bad_scale = dec!(1.5).serialize();
+        bad_scale[2] = 29;
+        assert!(borsh::from_slice::<Positive>(&bad_scale).is_err());
+
+        let mut bad_flags = dec!(1.5).serialize();
+        bad_flags[0] = 1;
+        assert!(borsh::from_slice::<Positive>(&bad_flags).is_err());
+
+        assert!(borsh::from_slice::<Positive>(&[0u8; 8]).is_err());
+    }
+}
@@ -0,0 +1,405 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 18/10/26
+******************************************************************************/
+
+//! Fallible arithmetic expressions over `Positive`.
+//!
+//! [`PositiveExpr`] wraps a `Positive` (or the first error encountered) and
+//! implements the arithmetic operators against `PositiveExpr`, `Positive`,
+//! `Decimal` and `f64` operands. Every step uses checked arithmetic; once a
+//! step fails, later steps are skipped and the error is returned by
+//! [`PositiveExpr::eval`] together with the chain of operations that led
+//! to it.
+//!
+//! ```rust
+//! use positive::{Positive, pos_or_panic};
+//!
+//! let notional = pos_or_panic!(25_000.0);
+//! let fee = (notional.expr() * 0.0004 + pos_or_panic!(1.5)).eval().unwrap();
+//! assert_eq!(fee, pos_or_panic!(11.5));
+//!
+//! let err = (notional.expr() - pos_or_panic!(30_000.0) * 2.0).eval();
+//! assert!(err.unwrap_err().to_string().contains("sub"));
+//! ```
+
+use crate::Positive;
+use crate::error::{Operation, PositiveError, PositiveResult};
+use crate::positive::round_div;
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::fmt;
+use core::ops::{Add, Div, Mul, Sub};
+use num_traits::FromPrimitive;
+use rust_decimal::Decimal;
+
+/// Checked arithmetic function applied by a single expression step.
+type CheckedOp = fn(Decimal, Decimal) -> Option<Decimal>;
+
+/// A `Positive` arithmetic expression that defers error handling to
+/// [`PositiveExpr::eval`].
+///
+/// Build one with [`Positive::expr`] or `PositiveExpr::from`, combine it with
+/// the usual operators and resolve it once at the end.
+#[derive(Debug)]
+pub struct PositiveExpr {
+    state: PositiveResult<Positive>,
+    operations: Vec<&'static str>,
+}
+
+impl PositiveExpr {
+    /// Starts an expression from a known `Positive` value.
+    #[must_use]
+    pub fn new(value: Positive) -> Self {
+        PositiveExpr {
+            state: Ok(value),
+            operations: Vec::new(),
+        }
+    }
+
+    /// Resolves the expression.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `ExpressionError` whose `chain` lists the operations up
+    /// to and including the one that failed, and whose `source` is the
+    /// first failure (an `OutOfBounds` negative result, an
+    /// `ArithmeticError` on overflow or division by zero, or a
+    /// `ConversionError` for an `f64` operand that is not representable
+    /// as `Decimal`).
+    pub fn eval(self) -> PositiveResult<Positive> {
+        self.state
+    }
+
+    /// Returns `true` if no step has failed so far.
+    #[must_use]
+    pub fn is_ok(&self) -> bool {
+        self.state.is_ok()
+    }
+
+    /// Returns the operations applied so far, ending with the failing one
+    /// if the expression has failed.
+    #[must_use]
+    pub fn operations(&self) -> &[&'static str] {
+        &self.operations
+    }
+
+    /// Applies one checked step, recording it in the operation chain.
+    fn step(
+        mut self,
+        name: &'static str,
+        operation: Operation,
+        rhs: PositiveResult<Decimal>,
+        apply: CheckedOp,
+    ) -> Self {
+        let Ok(lhs) = self.state else {
+            return self;
+        };
+        self.operations.push(name);
+        let result = rhs.and_then(|rhs| {
+            let value = apply(lhs.to_dec(), rhs)
+                .ok_or_else(|| PositiveError::arithmetic_error(operation, "overflow"))?;
+            Positive::new_decimal(value)
+        });
+        self.state = result.map_err(|err| self.failure(err));
+        self
+    }
+
+    /// Combines this expression with a right-hand expression: the
+    /// operations recorded by `rhs` are appended to the chain, then `apply`
+    /// is run on its value or its error is adopted. Nothing happens if this
+    /// expression failed first.
+    fn combine(mut self, rhs: PositiveExpr, apply: impl FnOnce(Self, Positive) -> Self) -> Self {
+        if self.state.is_err() {
+            return self;
+        }
+        self.operations.extend(rhs.operations);
+        match rhs.state {
+            Ok(value) => apply(self, value),
+            Err(err) => {
+                let source = match err {
+                    PositiveError::ExpressionError { source, .. } => *source,
+                    other => other,
+                };
+                self.state = Err(self.failure(source));
+                self
+            }
+        }
+    }
+
+    #[cold]
+    #[inline(never)]
+    fn failure(&self, source: PositiveError) -> PositiveError {
+        PositiveError::ExpressionError {
+            chain: self.operations.join(" -> "),
+            source: Box::new(source),
+        }
+    }
+}
+
+impl From<Positive> for PositiveExpr {
+    fn from(value: Positive) -> Self {
+        PositiveExpr::new(value)
+    }
+}
+
+/// Starts an expression from an earlier result. An error is returned by
+/// [`PositiveExpr::eval`] as is, and wrapped in an `ExpressionError` with
+/// the chain of any expression it is combined into.
+impl From<PositiveResult<Positive>> for PositiveExpr {
+    fn from(state: PositiveResult<Positive>) -> Self {
+        PositiveExpr {
+            state,
+            operations: Vec::new(),
+        }
+    }
+}
+
+impl fmt::Display for PositiveExpr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.state {
+            Ok(value) => write!(f, "{value}"),
+            Err(err) => write!(f, "<{err}>"),
+        }
+    }
+}
+
+impl Positive {
+    /// Starts a fallible [`PositiveExpr`] from this value.
+    #[must_use]
+    pub fn expr(self) -> PositiveExpr {
+        PositiveExpr::new(self)
+    }
+}
+
+fn checked_div_rounded(lhs: Decimal, rhs: Decimal) -> Option<Decimal> {
+    if rhs.is_zero() {
+        None
+    } else {
+        lhs.checked_div(rhs).map(round_div)
+    }
+}
+
+/// Converts an `f64` operand, reporting non-representable values.
+fn f64_operand(rhs: f64) -> PositiveResult<Decimal> {
+    Decimal::from_f64(rhs).ok_or_else(|| {
+        PositiveError::conversion_error("f64", "Decimal", "value not representable as Decimal")
+    })
+}
+
+/// Rejects a zero divisor before it is reported as an overflow.
+fn divisor(rhs: Decimal, operation: Operation) -> PositiveResult<Decimal> {
+    if rhs.is_zero() {
+        Err(PositiveError::arithmetic_error(
+            operation,
+            "division by zero",
+        ))
+    } else {
+        Ok(rhs)
+    }
+}
+
+macro_rules! impl_expr_op {
+    ($trait:ident, $method:ident, $op:expr, $apply:expr, $name:literal, $f64_name:literal, $dec_name:literal, $rhs_check:expr) => {
+        impl $trait<PositiveExpr> for PositiveExpr {
+            type Output = PositiveExpr;
+            #[inline]
+            fn $method(self, rhs: PositiveExpr) -> PositiveExpr {
+                self.combine(rhs, |lhs, value| lhs.$method(value))
+            }
+        }
+
+        impl $trait<Positive> for PositiveExpr {
+            type Output = PositiveExpr;
+            #[inline]
+            fn $method(self, rhs: Positive) -> PositiveExpr {
+                self.step($name, $op, $rhs_check(rhs.to_dec(), $op), $apply)
+            }
+        }
+
+        impl $trait<&Positive> for PositiveExpr {
+            type Output = PositiveExpr;
+            #[inline]
+            fn $method(self, rhs: &Positive) -> PositiveExpr {
+                self.$method(*rhs)
+            }
+        }
+
+        impl $trait<Decimal> for PositiveExpr {
+            type Output = PositiveExpr;
+            #[inline]
+            fn $method(self, rhs: Decimal) -> PositiveExpr {
+                self.step($dec_name, $op, $rhs_check(rhs, $op), $apply)
+            }
+        }
+
+        impl $trait<&Decimal> for PositiveExpr {
+            type Output = PositiveExpr;
+            #[inline]
+            fn $method(self, rhs: &Decimal) -> PositiveExpr {
+                self.$method(*rhs)
+            }
+        }
+
+        impl $trait<f64> for PositiveExpr {
+            type Output = PositiveExpr;
+            #[inline]
+            fn $method(self, rhs: f64) -> PositiveExpr {
+                let rhs = f64_operand(rhs).and_then(|rhs| $rhs_check(rhs, $op));
+                self.step($f64_name, $op, rhs, $apply)
+            }
+        }
+
+        impl $trait<PositiveExpr> for Positive {
+            type Output = PositiveExpr;
+            #[inline]
+            fn $method(self, rhs: PositiveExpr) -> PositiveExpr {
+                PositiveExpr::new(self).$method(rhs)
+            }
+        }
+    };
+}
+
+fn any_operand(rhs: Decimal, _operation: Operation) -> PositiveResult<Decimal> {
+    Ok(rhs)
+}
+
+impl_expr_op!(
+    Add,
+    add,
+    Operation::Add,
+    Decimal::checked_add,
+    "add",
+    "add_f64",
+    "add_decimal",
+    any_operand
+);
+impl_expr_op!(
+    Sub,
+    sub,
+    Operation::Sub,
+    Decimal::checked_sub,
+    "sub",
+    "sub_f64",
+    "sub_decimal",
+    any_operand
+);
+impl_expr_op!(
+    Mul,
+    mul,
+    Operation::Mul,
+    Decimal::checked_mul,
+    "mul",
+    "mul_f64",
+    "mul_decimal",
+    any_operand
+);
+impl_expr_op!(
+    Div,
+    div,
+    Operation::Div,
+    checked_div_rounded,
+    "div",
+    "div_f64",
+    "div_decimal",
+    divisor
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pos_or_panic;
+    use alloc::string::ToString;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_expr_success_matches_operators() {
+        let a = pos_or_panic!(10.0);
+        let b = pos_or_panic!(4.0);
+        let value = ((a.expr() + b) * dec!(2) / 4.0 - b).eval().unwrap();
+        assert_eq!(value, (a + b) * dec!(2) / 4.0 - b);
+    }
+
+    #[test]
+    fn test_expr_keeps_first_error_and_chain() {
+        let expr = (pos_or_panic!(1.0).expr() + pos_or_panic!(1.0) - pos_or_panic!(5.0)) / 0.0;
+        assert_eq!(expr.operations(), &["add", "sub"]);
+        let err = expr.eval().unwrap_err();
+        match err {
+            PositiveError::ExpressionError { chain, source } => {
+                assert_eq!(chain, "add -> sub");
+                assert_eq!(source.code(), "out_of_bounds");
+            }
+            other => panic!("unexpected error: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_expr_division_by_zero() {
+        let err = (pos_or_panic!(2.0).expr() / Decimal::ZERO)
+            .eval()
+            .unwrap_err();
+        assert!(err.to_string().contains("division by zero"));
+        assert!(err.to_string().contains("div_decimal"));
+    }
+
+    #[test]
+    fn test_expr_invalid_f64_operand() {
+        let err = (pos_or_panic!(2.0).expr() * f64::NAN).eval().unwrap_err();
+        assert!(err.to_string().contains("mul_f64"));
+        assert_eq!(err.root_cause().code(), "conversion");
+    }
+
+    #[test]
+    fn test_expr_overflow() {
+        let err = (Positive::INFINITY.expr() * 2.0).eval().unwrap_err();
+        assert!(err.to_string().contains("overflow"));
+    }
+
+    #[test]
+    fn test_expr_combines_expressions() {
+        let ok = pos_or_panic!(3.0).expr() * (pos_or_panic!(2.0).expr() + pos_or_panic!(1.0));
+        assert_eq!(ok.eval().unwrap(), pos_or_panic!(9.0));
+
+        let failed = pos_or_panic!(1.0).expr() - pos_or_panic!(2.0);
+        let combined = pos_or_panic!(3.0) + failed;
+        assert_eq!(combined.operations(), &["sub"]);
+        assert!(combined.eval().is_err());
+    }
+
+    #[test]
+    fn test_expr_combines_operation_chains() {
+        let rhs = pos_or_panic!(2.0).expr() * 3.0;
+        let combined = (pos_or_panic!(1.0).expr() + pos_or_panic!(1.0)) + rhs;
+        assert_eq!(combined.operations(), &["add", "mul_f64", "add"]);
+        assert_eq!(combined.eval().unwrap(), pos_or_panic!(8.0));
+
+        let failed = pos_or_panic!(1.0).expr() - pos_or_panic!(2.0);
+        let err = (pos_or_panic!(4.0).expr() * 2.0 + failed)
+            .eval()
+            .unwrap_err();
+        match err {
+            PositiveError::ExpressionError { chain, source } => {
+                assert_eq!(chain, "mul_f64 -> sub");
+                assert_eq!(source.code(), "out_of_bounds");
+            }
+            other => panic!("unexpected error: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_expr_wraps_raw_errors() {
+        let raw = Err(PositiveError::invalid_value(dec!(-1), "rejected upstream"));
+        let err = (pos_or_panic!(1.0).expr() * 2.0 + PositiveExpr::from(raw))
+            .eval()
+            .unwrap_err();
+        match err {
+            PositiveError::ExpressionError { chain, source } => {
+                assert_eq!(chain, "mul_f64");
+                assert_eq!(source.code(), "invalid_value");
+            }
+            other => panic!("unexpected error: {other:?}"),
+        }
+    }
+}
@@ -0,0 +1,522 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 18/10/26
+******************************************************************************/
+
+//! CSV validation and normalization of `Positive` columns.
+//!
+//! A [`PositiveReader`] streams the records of a CSV file and parses the
+//! named columns into `Positive` values with [`ParseOptions`]. Records
+//! whose fields all parse are yielded as [`Row`]s; the others are skipped
+//! and every failing field, as well as every malformed record (such as
+//! one with the wrong number of fields), is kept as a [`LineError`] with
+//! its line number, so one pass reports all the bad rows of a vendor file.
+//! [`PositiveReader::write_normalized`] writes the valid records back out
+//! with the named columns formatted at a fixed scale.
+//!
+//! I/O errors and a missing column are returned as `ConversionError`s.
+//!
+//! ```rust
+//! use positive::csv::{ParseOptions, PositiveReader};
+//!
+//! let data = "sku,price\na,\"1,234.5\"\nb,-0\nc,NaN\nd,99.125\n";
+//! let options = ParseOptions {
+//!     thousands_separator: Some(','),
+//!     ..ParseOptions::default()
+//! };
+//! let mut reader = PositiveReader::from_reader(data.as_bytes(), &["price"], options).unwrap();
+//!
+//! let mut output = Vec::new();
+//! reader.write_normalized(&mut output, 2).unwrap();
+//! assert_eq!(String::from_utf8(output).unwrap(), "sku,price\na,1234.50\nd,99.12\n");
+//!
+//! let lines: Vec<u64> = reader.errors().iter().map(|error| error.line).collect();
+//! assert_eq!(lines, [3, 4]);
+//! ```
+
+use crate::error::{PositiveError, PositiveResult};
+use crate::positive::Positive;
+use ::csv::{Reader, StringRecord, Writer};
+use core::fmt;
+use rust_decimal::Decimal;
+use std::io::{Read, Write};
+
+/// How CSV fields are parsed into `Positive` values.
+///
+/// The default is strict: plain decimals with a `.` separator, surrounding
+/// whitespace trimmed, and no grouping, exponents or negative zero.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ParseOptions {
+    /// Trim surrounding whitespace before parsing.
+    pub trim: bool,
+    /// Separator between groups of three integer digits, such as `,` in
+    /// `1,234.5`. Grouping is validated, so `1,23` is rejected.
+    pub thousands_separator: Option<char>,
+    /// Separator between the integer and fractional parts.
+    pub decimal_separator: char,
+    /// Accept scientific notation such as `1.5e3`.
+    pub allow_exponent: bool,
+    /// Read `-0` (and `-0.00`, ...) as zero instead of rejecting it.
+    pub allow_negative_zero: bool,
+}
+
+impl ParseOptions {
+    /// The default options.
+    pub const STRICT: ParseOptions = ParseOptions {
+        trim: true,
+        thousands_separator: None,
+        decimal_separator: '.',
+        allow_exponent: false,
+        allow_negative_zero: false,
+    };
+
+    /// Parses a single field.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ConversionError` if the thousands and decimal separators
+    /// are the same character, a `ParseError` for text that is not a
+    /// decimal under these options, an `InvalidValue` error for a rejected
+    /// negative zero, and the invariant error for a negative value.
+    pub fn parse(&self, field: &str) -> PositiveResult<Positive> {
+        self.check()?;
+        let text = if self.trim { field.trim() } else { field };
+        let text = self.normalize(text).map_err(|reason| {
+            PositiveError::parse_error(field, rust_decimal::Error::ErrorString(reason.into()))
+        })?;
+        let parsed = if !text.contains(['e', 'E']) {
+            text.parse::<Decimal>()
+        } else if self.allow_exponent {
+            Decimal::from_scientific(&text)
+        } else {
+            Err(rust_decimal::Error::ErrorString(
+                "exponent not allowed".into(),
+            ))
+        };
+        let mut value = parsed.map_err(|err| PositiveError::parse_error(field, err))?;
+        // `Decimal` parses `-0` as zero, so check the sign in the text.
+        if value.is_zero() && text.starts_with('-') {
+            if !self.allow_negative_zero {
+                return Err(PositiveError::invalid_value(value, "negative zero"));
+            }
+            value.set_sign_positive(true);
+        }
+        Positive::new_decimal(value)
+    }
+
+    /// Rejects options whose thousands separator is also the decimal
+    /// separator, which would make `1,234` ambiguous.
+    fn check(&self) -> PositiveResult<()> {
+        if self.thousands_separator == Some(self.decimal_separator) {
+            return Err(PositiveError::conversion_error(
+                "CSV",
+                "Positive",
+                &format!(
+                    "`{}` cannot be both the thousands and the decimal separator",
+                    self.decimal_separator
+                ),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Removes the thousands separators and replaces the decimal separator
+    /// with `.`.
+    fn normalize(&self, text: &str) -> Result<String, &'static str> {
+        let (integer, fraction) = match text.split_once(self.decimal_separator) {
+            Some((integer, fraction)) => (integer, Some(fraction)),
+            None => (text, None),
+        };
+        let mut normalized = String::with_capacity(text.len());
+        match self.thousands_separator {
+            Some(separator) if integer.contains(separator) => {
+                let digits = integer.trim_start_matches(['+', '-']);
+                normalized.push_str(&integer[..integer.len() - digits.len()]);
+                for (index, group) in digits.split(separator).enumerate() {
+                    let valid = group.bytes().all(|byte| byte.is_ascii_digit())
+                        && match index {
+                            0 => (1..=3).contains(&group.len()),
+                            _ => group.len() == 3,
+                        };
+                    if !valid {
+                        return Err("invalid digit grouping");
+                    }
+                    normalized.push_str(group);
+                }
+            }
+            _ => normalized.push_str(integer),
+        }
+        if let Some(fraction) = fraction {
+            normalized.push('.');
+            normalized.push_str(fraction);
+        }
+        Ok(normalized)
+    }
+}
+
+impl Default for ParseOptions {
+    fn default() -> Self {
+        ParseOptions::STRICT
+    }
+}
+
+/// A field that failed to parse, or a malformed record.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LineError {
+    /// The line on which the record starts, counting from 1.
+    pub line: u64,
+    /// The name of the column, or `None` when the record itself could not
+    /// be read.
+    pub column: Option<String>,
+    /// Why the field is not a valid `Positive`, or why the record is
+    /// malformed.
+    pub error: PositiveError,
+}
+
+impl fmt::Display for LineError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.column {
+            Some(column) => write!(f, "line {}, column `{column}`: {}", self.line, self.error),
+            None => write!(f, "line {}: {}", self.line, self.error),
+        }
+    }
+}
+
+impl core::error::Error for LineError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+/// A record whose named columns all hold valid `Positive` values.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Row {
+    line: u64,
+    record: StringRecord,
+    values: Vec<Positive>,
+}
+
+impl Row {
+    /// The line on which the record starts, counting from 1.
+    #[must_use]
+    pub fn line(&self) -> u64 {
+        self.line
+    }
+
+    /// The raw record, including the columns that were not parsed.
+    #[must_use]
+    pub fn record(&self) -> &StringRecord {
+        &self.record
+    }
+
+    /// The parsed values, in the order the columns were named.
+    #[must_use]
+    pub fn values(&self) -> &[Positive] {
+        &self.values
+    }
+}
+
+/// A streaming reader that parses named CSV columns into `Positive`.
+pub struct PositiveReader<R> {
+    reader: Reader<R>,
+    headers: StringRecord,
+    columns: Vec<(String, usize)>,
+    options: ParseOptions,
+    errors: Vec<LineError>,
+    done: bool,
+}
+
+impl<R: Read> PositiveReader<R> {
+    /// Reads `input` as CSV with a header row, using the default `csv`
+    /// settings.
+    ///
+    /// # Errors
+    ///
+    /// See [`PositiveReader::new`].
+    pub fn from_reader(input: R, columns: &[&str], options: ParseOptions) -> PositiveResult<Self> {
+        PositiveReader::new(Reader::from_reader(input), columns, options)
+    }
+
+    /// Wraps a configured `csv::Reader` whose first record holds the
+    /// column names.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ConversionError` if the thousands and decimal separators
+    /// of `options` are the same character, the header cannot be read or
+    /// a named column is missing from it.
+    pub fn new(
+        mut reader: Reader<R>,
+        columns: &[&str],
+        options: ParseOptions,
+    ) -> PositiveResult<Self> {
+        options.check()?;
+        let headers = reader.headers().map_err(csv_error)?.clone();
+        let columns = columns
+            .iter()
+            .map(|&name| {
+                headers
+                    .iter()
+                    .position(|header| header == name)
+                    .map(|index| (name.to_string(), index))
+                    .ok_or_else(|| {
+                        PositiveError::conversion_error(
+                            "CSV",
+                            "Positive",
+                            &format!("missing column `{name}`"),
+                        )
+                    })
+            })
+            .collect::<PositiveResult<_>>()?;
+        Ok(PositiveReader {
+            reader,
+            headers,
+            columns,
+            options,
+            errors: Vec::new(),
+            done: false,
+        })
+    }
+
+    /// The header record.
+    #[must_use]
+    pub fn headers(&self) -> &StringRecord {
+        &self.headers
+    }
+
+    /// The fields and records rejected so far.
+    #[must_use]
+    pub fn errors(&self) -> &[LineError] {
+        &self.errors
+    }
+
+    /// Consumes the reader, returning the fields and records rejected so
+    /// far.
+    #[must_use]
+    pub fn into_errors(self) -> Vec<LineError> {
+        self.errors
+    }
+
+    /// Writes the header and every remaining valid record to `output`,
+    /// with the named columns formatted at `scale` decimal places.
+    ///
+    /// Rejected records are left out and kept in
+    /// [`errors`](PositiveReader::errors).
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ConversionError` if reading or writing fails with an I/O
+    /// error.
+    pub fn write_normalized<W: Write>(&mut self, output: W, scale: u32) -> PositiveResult<()> {
+        let mut writer = Writer::from_writer(output);
+        writer.write_record(&self.headers).map_err(csv_error)?;
+        let mut fields: Vec<String> = Vec::with_capacity(self.headers.len());
+        while let Some(row) = self.next() {
+            let row = row?;
+            fields.clear();
+            fields.extend(row.record.iter().map(str::to_string));
+            for ((_, index), value) in self.columns.iter().zip(&row.values) {
+                fields[*index] = value.format_fixed_places(scale);
+            }
+            writer.write_record(&fields).map_err(csv_error)?;
+        }
+        writer.flush().map_err(|err| csv_error(err.into()))
+    }
+
+    /// Parses the named columns of `record`, recording any failure.
+    fn parse(&mut self, record: StringRecord) -> Option<Row> {
+        let line = record.position().map_or(0, |position| position.line());
+        let mut values = Vec::with_capacity(self.columns.len());
+        let mut valid = true;
+        for (column, index) in &self.columns {
+            match self.options.parse(record.get(*index).unwrap_or_default()) {
+                Ok(value) => values.push(value),
+                Err(error) => {
+                    valid = false;
+                    self.errors.push(LineError {
+                        line,
+                        column: Some(column.clone()),
+                        error,
+                    });
+                }
+            }
+        }
+        valid.then_some(Row {
+            line,
+            record,
+            values,
+        })
+    }
+}
+
+impl<R: Read> Iterator for PositiveReader<R> {
+    type Item = PositiveResult<Row>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.done {
+            let mut record = StringRecord::new();
+            match self.reader.read_record(&mut record) {
+                Ok(true) => {
+                    if let Some(row) = self.parse(record) {
+                        return Some(Ok(row));
+                    }
+                }
+                Ok(false) => self.done = true,
+                Err(err) if err.is_io_error() => {
+                    self.done = true;
+                    return Some(Err(csv_error(err)));
+                }
+                Err(err) => self.errors.push(LineError {
+                    line: err.position().map_or(0, |position| position.line()),
+                    column: None,
+                    error: csv_error(err),
+                }),
+            }
+        }
+        None
+    }
+}
+
+fn csv_error(err: ::csv::Error) -> PositiveError {
+    PositiveError::conversion_error("CSV", "Positive", &err.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn parse(text: &str, options: ParseOptions) -> PositiveResult<Decimal> {
+        options.parse(text).map(|value| value.to_dec())
+    }
+
+    #[test]
+    fn test_strict_parsing() {
+        let strict = ParseOptions::default();
+        assert_eq!(parse(" 1.50 ", strict).unwrap(), dec!(1.50));
+        assert_eq!(parse("NaN", strict).unwrap_err().code(), "parse");
+        assert_eq!(parse("1,234.5", strict).unwrap_err().code(), "parse");
+        assert_eq!(parse("1e3", strict).unwrap_err().code(), "parse");
+        assert_eq!(parse("-1", strict).unwrap_err().code(), "out_of_bounds");
+        assert_eq!(parse("-0", strict).unwrap_err().code(), "invalid_value");
+        assert_eq!(parse("-0.00", strict).unwrap_err().code(), "invalid_value");
+        let untrimmed = ParseOptions {
+            trim: false,
+            ..strict
+        };
+        assert_eq!(parse(" 1", untrimmed).unwrap_err().code(), "parse");
+    }
+
+    #[test]
+    fn test_configured_parsing() {
+        let options = ParseOptions {
+            thousands_separator: Some('.'),
+            decimal_separator: ',',
+            allow_exponent: true,
+            allow_negative_zero: true,
+            ..ParseOptions::STRICT
+        };
+        assert_eq!(parse("1.234.567,5", options).unwrap(), dec!(1234567.5));
+        assert_eq!(parse("12,25", options).unwrap(), dec!(12.25));
+        assert_eq!(parse("1,5e3", options).unwrap(), dec!(1500));
+        if cfg!(feature = "non-zero") {
+            assert_eq!(parse("-0", options).unwrap_err().code(), "out_of_bounds");
+        } else {
+            assert!(!parse("-0", options).unwrap().is_sign_negative());
+        }
+        assert_eq!(parse("1.23,5", options).unwrap_err().code(), "parse");
+        assert_eq!(parse("1234.567", options).unwrap_err().code(), "parse");
+    }
+
+    #[test]
+    fn test_separators_must_differ() {
+        let options = ParseOptions {
+            thousands_separator: Some(','),
+            decimal_separator: ',',
+            ..ParseOptions::STRICT
+        };
+        assert_eq!(parse("1,5", options).unwrap_err().code(), "conversion");
+        let err = PositiveReader::from_reader("price\n1\n".as_bytes(), &["price"], options)
+            .err()
+            .unwrap();
+        assert_eq!(err.code(), "conversion");
+    }
+
+    #[test]
+    fn test_reader_collects_errors() {
+        let data = "id,price,qty\n1,10.5,2\n2,-1,x\n3,7,\n4,0.25,4\n";
+        let mut reader =
+            PositiveReader::from_reader(data.as_bytes(), &["qty", "price"], ParseOptions::STRICT)
+                .unwrap();
+        let rows: Vec<Row> = reader.by_ref().collect::<PositiveResult<_>>().unwrap();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].line(), 2);
+        assert_eq!(rows[1].record().get(0), Some("4"));
+        assert_eq!(rows[1].values()[0].to_dec(), dec!(4));
+        assert_eq!(rows[1].values()[1].to_dec(), dec!(0.25));
+
+        let errors = reader.into_errors();
+        let found: Vec<(u64, &str, &str)> = errors
+            .iter()
+            .map(|error| {
+                (
+                    error.line,
+                    error.column.as_deref().unwrap(),
+                    error.error.code(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            found,
+            [
+                (3, "qty", "parse"),
+                (3, "price", "out_of_bounds"),
+                (4, "qty", "parse"),
+            ]
+        );
+        assert!(
+            errors[1]
+                .to_string()
+                .starts_with("line 3, column `price`: ")
+        );
+    }
+
+    #[test]
+    fn test_missing_column_and_malformed_record() {
+        let err =
+            PositiveReader::from_reader("a,b\n1,2\n".as_bytes(), &["c"], ParseOptions::STRICT)
+                .err()
+                .unwrap();
+        assert_eq!(err.code(), "conversion");
+
+        let mut reader =
+            PositiveReader::from_reader("a,b\n1\n2,3\n".as_bytes(), &["a"], ParseOptions::STRICT)
+                .unwrap();
+        assert_eq!(
+            reader.next().unwrap().unwrap().values()[0].to_dec(),
+            dec!(2)
+        );
+        assert!(reader.next().is_none());
+        let errors = reader.errors();
+        assert_eq!(errors.len(), 1);
+        assert_eq!((errors[0].line, errors[0].column.as_deref()), (2, None));
+        assert_eq!(errors[0].error.code(), "conversion");
+        assert!(errors[0].to_string().starts_with("line 2: "));
+    }
+
+    #[test]
+    fn test_write_normalized() {
+        let data = "price,note\n1.005,a\n2,\"b, c\"\nbad,d\nshort\n4,e\n";
+        let mut reader =
+            PositiveReader::from_reader(data.as_bytes(), &["price"], ParseOptions::STRICT).unwrap();
+        let mut output = Vec::new();
+        reader.write_normalized(&mut output, 3).unwrap();
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "price,note\n1.005,a\n2.000,\"b, c\"\n4.000,e\n"
+        );
+        let lines: Vec<u64> = reader.errors().iter().map(|error| error.line).collect();
+        assert_eq!(lines, [4, 5]);
+    }
+}
@@ -0,0 +1,334 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 18/10/26
+******************************************************************************/
+
+//! `polars` conversions for `Positive` columns.
+//!
+//! [`to_series`] builds a `Decimal` series that stores every value exactly,
+//! at the largest scale among them, and `Series::new` accepts `Positive`
+//! slices, vectors and arrays through `NamedFrom`. [`from_series`] reads a
+//! column back, and [`invalid_rows`] scans a `DataFrame` column and
+//! reports every row that breaks the invariant, so a prototype can be
+//! checked before it is ported to typed code.
+//!
+//! Both readers accept `Decimal`, float and integer columns. Floats go
+//! through [`Positive::new`], so they follow the same rules as any other
+//! `f64`.
+//!
+//! ```rust
+//! use polars::prelude::*;
+//! use positive::{Positive, dataframe, pos_or_panic};
+//!
+//! let prices = vec![pos_or_panic!(101.25), pos_or_panic!(99.5)];
+//! let series = Series::new("price".into(), prices.clone());
+//! assert_eq!(series.dtype(), &DataType::Decimal(Some(38), Some(2)));
+//! assert_eq!(dataframe::from_series(&series).unwrap(), prices);
+//!
+//! let df = df!("qty" => [1.5, -2.0, 3.0]).unwrap();
+//! let invalid = dataframe::invalid_rows(&df, "qty").unwrap();
+//! assert_eq!(invalid.len(), 1);
+//! assert_eq!(invalid[0].0, 1);
+//! ```
+
+use crate::binary::MAX_MANTISSA;
+use crate::error::{PositiveError, PositiveResult};
+use crate::positive::Positive;
+use polars::prelude::{
+    DataFrame, DataType, Int128Chunked, IntoSeries, NamedFrom, PlSmallStr, PolarsError, Series,
+};
+use rust_decimal::Decimal;
+
+/// Precision of the `Decimal` series built from `Positive` values.
+const PRECISION: usize = 38;
+
+/// Builds a `Decimal` series holding `values` exactly.
+///
+/// The scale of the series is the largest scale among `values`.
+///
+/// # Errors
+///
+/// Returns a `ConversionError` if the values cannot share that scale
+/// within 38 digits, which needs both very large values and many decimal
+/// places.
+pub fn to_series(name: PlSmallStr, values: &[Positive]) -> PositiveResult<Series> {
+    let scale = values
+        .iter()
+        .map(|value| value.to_dec().scale())
+        .max()
+        .unwrap_or(0);
+    let limit = 10i128.pow(PRECISION as u32);
+    let mut raw = Vec::with_capacity(values.len());
+    for (row, value) in values.iter().enumerate() {
+        let decimal = value.to_dec();
+        let mantissa = 10i128
+            .checked_pow(scale - decimal.scale())
+            .and_then(|factor| decimal.mantissa().checked_mul(factor))
+            .filter(|mantissa| *mantissa < limit)
+            .ok_or_else(|| {
+                PositiveError::conversion_error(
+                    "Positive",
+                    "Series",
+                    &format!(
+                        "value {decimal} at row {row} does not fit Decimal({PRECISION}, {scale})"
+                    ),
+                )
+            })?;
+        raw.push(mantissa);
+    }
+    Int128Chunked::from_vec(name, raw)
+        .into_decimal(Some(PRECISION), scale as usize)
+        .map(IntoSeries::into_series)
+        .map_err(polars_error)
+}
+
+/// Reads every row of a `Decimal`, float or integer series.
+///
+/// # Errors
+///
+/// Returns a `ConversionError` for a non-numeric series or a null row,
+/// and the error of the first row that is not a valid `Positive`.
+pub fn from_series(series: &Series) -> PositiveResult<Vec<Positive>> {
+    let mut values = Vec::with_capacity(series.len());
+    let mut first_error = None;
+    for_each_row(series, |row, value| {
+        if first_error.is_some() {
+            return;
+        }
+        match value {
+            Some(Ok(value)) => values.push(value),
+            Some(Err(err)) => first_error = Some(err),
+            None => {
+                first_error = Some(PositiveError::conversion_error(
+                    "null",
+                    "Positive",
+                    &format!("row {row} is null"),
+                ))
+            }
+        }
+    })?;
+    match first_error {
+        Some(err) => Err(err),
+        None => Ok(values),
+    }
+}
+
+/// Returns the rows of `column` that are not valid `Positive` values,
+/// with the error of each. Null rows are skipped.
+///
+/// # Errors
+///
+/// Returns a `ConversionError` if the column is missing or not numeric.
+pub fn invalid_rows(df: &DataFrame, column: &str) -> PositiveResult<Vec<(usize, PositiveError)>> {
+    let series = df
+        .column(column)
+        .map_err(polars_error)?
+        .as_materialized_series();
+    let mut invalid = Vec::new();
+    for_each_row(series, |row, value| {
+        if let Some(Err(err)) = value {
+            invalid.push((row, err));
+        }
+    })?;
+    Ok(invalid)
+}
+
+/// Converts each row of `series`, passing `None` for nulls.
+fn for_each_row<F>(series: &Series, mut visit: F) -> PositiveResult<()>
+where
+    F: FnMut(usize, Option<PositiveResult<Positive>>),
+{
+    match series.dtype() {
+        DataType::Decimal(_, _) => {
+            let decimals = series.decimal().map_err(polars_error)?;
+            let scale = decimals.scale();
+            for (row, mantissa) in decimals.physical().iter().enumerate() {
+                visit(row, mantissa.map(|mantissa| from_parts(mantissa, scale)));
+            }
+        }
+        dtype if dtype.is_float() => {
+            let floats = series.cast(&DataType::Float64).map_err(polars_error)?;
+            for (row, value) in floats.f64().map_err(polars_error)?.iter().enumerate() {
+                visit(row, value.map(Positive::new));
+            }
+        }
+        dtype if dtype.is_unsigned_integer() => {
+            let integers = series.cast(&DataType::UInt64).map_err(polars_error)?;
+            for (row, value) in integers.u64().map_err(polars_error)?.iter().enumerate() {
+                visit(
+                    row,
+                    value.map(|value| Positive::new_decimal(Decimal::from(value))),
+                );
+            }
+        }
+        dtype if dtype.is_signed_integer() => {
+            let integers = series.cast(&DataType::Int64).map_err(polars_error)?;
+            for (row, value) in integers.i64().map_err(polars_error)?.iter().enumerate() {
+                visit(
+                    row,
+                    value.map(|value| Positive::new_decimal(Decimal::from(value))),
+                );
+            }
+        }
+        dtype => {
+            return Err(PositiveError::conversion_error(
+                &dtype.to_string(),
+                "Positive",
+                "expected a Decimal, float or integer column",
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Rebuilds a value from a `Decimal` series row, dropping trailing zeros
+/// beyond the 28 decimal places or the 96-bit mantissa a `Decimal`
+/// supports.
+fn from_parts(mut mantissa: i128, mut scale: usize) -> PositiveResult<Positive> {
+    while scale > 0
+        && (scale > Decimal::MAX_SCALE as usize || mantissa.unsigned_abs() > MAX_MANTISSA)
+        && mantissa % 10 == 0
+    {
+        mantissa /= 10;
+        scale -= 1;
+    }
+    if scale > Decimal::MAX_SCALE as usize {
+        return Err(PositiveError::conversion_error(
+            "Decimal",
+            "Positive",
+            "more than 28 decimal places",
+        ));
+    }
+    crate::binary::from_parts(mantissa.unsigned_abs(), mantissa < 0, scale as u32)
+}
+
+fn polars_error(err: PolarsError) -> PositiveError {
+    PositiveError::conversion_error("Series", "Positive", &err.to_string())
+}
+
+/// Panics if the values cannot share a scale; see [`to_series`].
+impl NamedFrom<&[Positive], Positive> for Series {
+    fn new(name: PlSmallStr, values: &[Positive]) -> Self {
+        to_series(name, values).unwrap_or_else(|err| panic!("{err}"))
+    }
+}
+
+/// Panics if the values cannot share a scale; see [`to_series`].
+impl NamedFrom<Vec<Positive>, Positive> for Series {
+    fn new(name: PlSmallStr, values: Vec<Positive>) -> Self {
+        Series::new(name, values.as_slice())
+    }
+}
+
+/// Panics if the values cannot share a scale; see [`to_series`].
+impl<const N: usize> NamedFrom<[Positive; N], Positive> for Series {
+    fn new(name: PlSmallStr, values: [Positive; N]) -> Self {
+        Series::new(name, values.as_slice())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use polars::prelude::{Column, IntoColumn};
+    use rust_decimal_macros::dec;
+
+    fn positives(values: &[Decimal]) -> Vec<Positive> {
+        values
+            .iter()
+            .map(|&value| Positive::new_decimal(value).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_round_trip_uses_largest_scale() {
+        let values = positives(&[dec!(1.5), dec!(0.001), dec!(42)]);
+        let series = Series::new("v".into(), values.clone());
+        assert_eq!(series.dtype(), &DataType::Decimal(Some(38), Some(3)));
+        let raw: Vec<_> = series.decimal().unwrap().physical().iter().collect();
+        assert_eq!(raw, [Some(1500), Some(1), Some(42000)]);
+        assert_eq!(from_series(&series).unwrap(), values);
+    }
+
+    #[test]
+    fn test_round_trip_beyond_decimal_mantissa() {
+        let values = positives(&[dec!(1e10), dec!(1e-19)]);
+        let series = to_series("v".into(), &values).unwrap();
+        assert_eq!(series.dtype(), &DataType::Decimal(Some(38), Some(19)));
+        assert_eq!(from_series(&series).unwrap(), values);
+
+        let exact = Int128Chunked::from_vec("v".into(), vec![10i128.pow(29) + 1])
+            .into_decimal(Some(38), 19)
+            .unwrap()
+            .into_series();
+        assert_eq!(from_series(&exact).unwrap_err().code(), "conversion");
+    }
+
+    #[test]
+    fn test_to_series_rejects_unrepresentable_mix() {
+        let values = [
+            Positive::INFINITY,
+            Positive::new_decimal(dec!(0.0000000000000000000000000001)).unwrap(),
+        ];
+        assert_eq!(
+            to_series("v".into(), &values).unwrap_err().code(),
+            "conversion"
+        );
+    }
+
+    #[test]
+    fn test_from_series_numeric_columns() {
+        let floats = Series::new("f".into(), [2.5f64, 4.0]);
+        assert_eq!(
+            from_series(&floats).unwrap(),
+            positives(&[dec!(2.5), dec!(4)])
+        );
+        let integers = Series::new("i".into(), [3i32, 7]);
+        assert_eq!(
+            from_series(&integers).unwrap(),
+            positives(&[dec!(3), dec!(7)])
+        );
+        let unsigned = Series::new("u".into(), [u64::MAX]);
+        assert_eq!(
+            from_series(&unsigned).unwrap()[0].to_dec(),
+            Decimal::from(u64::MAX)
+        );
+    }
+
+    #[test]
+    fn test_from_series_errors() {
+        let negative = Series::new("v".into(), [1.0f64, -1.0]);
+        assert_eq!(from_series(&negative).unwrap_err().code(), "out_of_bounds");
+        let nulls = Series::new("v".into(), [Some(1.0f64), None]);
+        assert_eq!(from_series(&nulls).unwrap_err().code(), "conversion");
+        let strings = Series::new("v".into(), ["1.0"]);
+        assert_eq!(from_series(&strings).unwrap_err().code(), "conversion");
+    }
+
+    #[test]
+    fn test_decimal_scale_above_28() {
+        let exact = Int128Chunked::from_vec("v".into(), vec![15 * 10i128.pow(29)])
+            .into_decimal(Some(38), 30)
+            .unwrap()
+            .into_series();
+        assert_eq!(from_series(&exact).unwrap()[0].to_dec(), dec!(1.5));
+        let lossy = Int128Chunked::from_vec("v".into(), vec![15])
+            .into_decimal(Some(38), 30)
+            .unwrap()
+            .into_series();
+        assert_eq!(from_series(&lossy).unwrap_err().code(), "conversion");
+    }
+
+    #[test]
+    fn test_invalid_rows() {
+        let qty: Column =
+            Series::new("qty".into(), [Some(1.0f64), Some(-2.0), None, Some(-0.5)]).into_column();
+        let df = DataFrame::new(vec![qty]).unwrap();
+        let invalid = invalid_rows(&df, "qty").unwrap();
+        let rows: Vec<_> = invalid.iter().map(|(row, _)| *row).collect();
+        assert_eq!(rows, [1, 3]);
+        assert!(invalid.iter().all(|(_, err)| err.code() == "out_of_bounds"));
+        assert!(invalid_rows(&df, "missing").is_err());
+    }
+}
@@ -0,0 +1,229 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 18/10/26
+******************************************************************************/
+
+//! Structured errors for failed deserialization.
+//!
+//! Serde formats only carry an error *message*, so a failure while
+//! deserializing a nested `Positive` reaches the caller as plain text with
+//! no indication of where it happened. [`deserialize_with_path`] runs a
+//! deserializer, tracks the path of the value being decoded, and turns a
+//! failure into a [`PositiveError::DeserializeError`] that records that
+//! path next to the message. When the message is one raised by `Positive`
+//! itself, the structured error is rebuilt from it and kept as `source`.
+//!
+//! ```rust
+//! use positive::{Positive, PositiveError};
+//! use std::collections::BTreeMap;
+//!
+//! let json = r#"{"bid": 1.5, "ask": -2}"#;
+//! let mut de = serde_json::Deserializer::from_str(json);
+//! let err = positive::de::deserialize_with_path::<BTreeMap<String, Positive>, _>(&mut de)
+//!     .unwrap_err();
+//!
+//! match &err {
+//!     PositiveError::DeserializeError { path, message, source } => {
+//!         assert_eq!(path, "ask");
+//!         assert!(message.starts_with("Value -2 is out of bounds"));
+//!         assert_eq!(source.as_ref().unwrap().code(), "out_of_bounds");
+//!     }
+//!     other => panic!("unexpected error: {other}"),
+//! }
+//! assert_eq!(err.code(), "deserialize");
+//! assert_eq!(err.root_cause().code(), "out_of_bounds");
+//! ```
+
+use crate::error::PositiveError;
+use alloc::boxed::Box;
+use alloc::string::ToString;
+use core::fmt::Display;
+use serde::Deserialize;
+
+/// Reports a `PositiveError` raised while deserializing a `Positive`
+/// through the deserializer's own error type, using its display text as
+/// the message. [`deserialize_with_path`] parses that text back into the
+/// error.
+#[cold]
+#[inline(never)]
+pub(crate) fn custom<E>(error: PositiveError) -> E
+where
+    E: serde::de::Error,
+{
+    E::custom(error)
+}
+
+/// Deserializes a `T`, converting any failure into a
+/// [`PositiveError::DeserializeError`] that records the path to the
+/// failing value.
+///
+/// Works with any serde deserializer, e.g.
+/// `&mut serde_json::Deserializer::from_str(json)`.
+///
+/// # Errors
+///
+/// Returns a `DeserializeError` with the path and the deserializer's
+/// message for any failure: values rejected by `Positive`, missing fields,
+/// syntax errors, wrong types, ... For values rejected by `Positive`,
+/// `source` holds the structured error.
+pub fn deserialize_with_path<'de, T, D>(deserializer: D) -> Result<T, PositiveError>
+where
+    T: Deserialize<'de>,
+    D: serde::Deserializer<'de>,
+    D::Error: Display,
+{
+    serde_path_to_error::deserialize(deserializer).map_err(|err| {
+        let path = err.path().to_string();
+        let message = err.into_inner().to_string();
+        PositiveError::DeserializeError {
+            path,
+            source: recover(&message).map(Box::new),
+            message,
+        }
+    })
+}
+
+/// Rebuilds the error raised through [`custom`] from a deserializer's
+/// message.
+///
+/// The message must be exactly the display text of an `OutOfBounds`,
+/// `InvalidValue`, `InvalidPrecision` or `ConversionError`, the errors a
+/// `Positive` can be rejected with, apart from the position that text
+/// formats append (` at line 1 column 9`).
+fn recover(message: &str) -> Option<PositiveError> {
+    let message = strip_position(message);
+    let error = if let Some(rest) = message.strip_prefix("Value ") {
+        let (value, rest) = rest.split_once(" is out of bounds (min: ")?;
+        let (min, max) = rest.strip_suffix(')')?.split_once(", max: ")?;
+        PositiveError::out_of_bounds(value.parse().ok()?, min.parse().ok()?, max.parse().ok()?)
+    } else if let Some(rest) = message.strip_prefix("Invalid positive value ") {
+        let (value, reason) = rest.split_once(": ")?;
+        PositiveError::invalid_value(value.parse().ok()?, reason)
+    } else if let Some(rest) = message.strip_prefix("Invalid precision ") {
+        let (precision, reason) = rest.split_once(": ")?;
+        PositiveError::invalid_precision(precision.parse().ok()?, reason)
+    } else if let Some(rest) = message.strip_prefix("Failed to convert from ") {
+        let (types, reason) = rest.split_once(": ")?;
+        let (from_type, to_type) = types.split_once(" to ")?;
+        PositiveError::conversion_error(from_type, to_type, reason)
+    } else {
+        return None;
+    };
+    // Only accept text the rebuilt error displays identically.
+    (error.to_string() == message).then_some(error)
+}
+
+/// Removes a trailing ` at line L column C`.
+fn strip_position(message: &str) -> &str {
+    let is_number = |text: &str| !text.is_empty() && text.bytes().all(|b| b.is_ascii_digit());
+    match message.rsplit_once(" at line ") {
+        Some((head, position))
+            if position
+                .split_once(" column ")
+                .is_some_and(|(line, column)| is_number(line) && is_number(column)) =>
+        {
+            head
+        }
+        _ => message,
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::Positive;
+    use rust_decimal_macros::dec;
+    use std::error::Error as _;
+
+    #[derive(Debug, Deserialize)]
+    #[allow(dead_code)]
+    struct Order {
+        price: Positive,
+        legs: Vec<Positive>,
+    }
+
+    fn parse(json: &str) -> Result<Order, PositiveError> {
+        deserialize_with_path(&mut serde_json::Deserializer::from_str(json))
+    }
+
+    #[test]
+    fn test_nested_positive_failure_has_path() {
+        let err = parse(r#"{"price": 1, "legs": [1, -3]}"#).unwrap_err();
+        match &err {
+            PositiveError::DeserializeError {
+                path,
+                message,
+                source,
+            } => {
+                assert_eq!(path, "legs[1]");
+                assert!(message.starts_with("Value -3 is out of bounds"));
+                assert!(matches!(
+                    source.as_deref(),
+                    Some(PositiveError::OutOfBounds { value, .. }) if *value == dec!(-3)
+                ));
+            }
+            other => panic!("unexpected error: {other:?}"),
+        }
+        assert!(err.source().is_some());
+        assert_eq!(err.root_cause().code(), "out_of_bounds");
+    }
+
+    #[test]
+    fn test_string_failure_has_path() {
+        let err = parse(r#"{"price": "abc", "legs": []}"#).unwrap_err();
+        match err {
+            PositiveError::DeserializeError {
+                path,
+                message,
+                source,
+            } => {
+                assert_eq!(path, "price");
+                assert!(message.starts_with("Failed to convert from string to Positive"));
+                assert_eq!(
+                    source.as_deref(),
+                    Some(&PositiveError::conversion_error(
+                        "string",
+                        "Positive",
+                        "invalid string 'abc', expected a positive number"
+                    ))
+                );
+            }
+            other => panic!("unexpected error: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_missing_field() {
+        let err = parse(r#"{"price": 1}"#).unwrap_err();
+        match err {
+            PositiveError::DeserializeError {
+                message, source, ..
+            } => {
+                assert!(message.contains("legs"));
+                assert!(source.is_none());
+            }
+            other => panic!("unexpected error: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_recover_requires_exact_text() {
+        let error = PositiveError::invalid_precision(30, "scale exceeds 28");
+        assert_eq!(recover(&error.to_string()), Some(error.clone()));
+        assert_eq!(
+            recover(&format!("{error} at line 3 column 14")),
+            Some(error)
+        );
+        assert_eq!(recover("Value -1 is out of bounds"), None);
+        assert_eq!(recover("Value x is out of bounds (min: 0, max: 1)"), None);
+        assert_eq!(recover("Invalid precision 30"), None);
+        assert_eq!(recover("invalid type: string, expected a number"), None);
+    }
+
+    #[test]
+    fn test_success() {
+        let order = parse(r#"{"price": 2.5, "legs": []}"#).unwrap();
+        assert_eq!(order.price, Positive::TWO + Positive::new(0.5).unwrap());
+    }
+}
@@ -0,0 +1,290 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 18/10/26
+******************************************************************************/
+
+//! BSON `Decimal128` support for `Positive`.
+//!
+//! MongoDB stores exact decimals as IEEE 754-2008 `Decimal128`, with 34
+//! significant digits and an exponent between -6176 and 6111. Every
+//! `Positive` fits, so `From<Positive> for Decimal128` is exact and keeps
+//! the scale. The other direction is fallible:
+//! `TryFrom<Decimal128> for Positive` strips trailing zeros when it can,
+//! and otherwise fails with a `ConversionError` rather than rounding when
+//! the value has more than 28 decimal places or does not fit in a
+//! `Decimal`. `NaN`, infinities and negative values are rejected too.
+//!
+//! The default `serde` impls write `Positive` as a BSON double. Use this
+//! module with `#[serde(with = "positive::decimal128")]` to write a
+//! `Decimal128` instead; formats other than BSON receive the extended JSON
+//! form `{"$numberDecimal": "..."}`. Deserializing accepts `Decimal128`,
+//! double, integer and string values, so existing documents stay
+//! readable.
+//!
+//! ```rust
+//! use bson::{Bson, Decimal128, doc};
+//! use positive::{Positive, pos_or_panic};
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Fill {
+//!     #[serde(with = "positive::decimal128")]
+//!     price: Positive,
+//! }
+//!
+//! let fill = Fill { price: pos_or_panic!(101.25) };
+//! let document = bson::to_document(&fill).unwrap();
+//! assert_eq!(document, doc! { "price": Decimal128::from(pos_or_panic!(101.25)) });
+//!
+//! let back: Fill = bson::from_document(doc! { "price": 99.5 }).unwrap();
+//! assert_eq!(back.price, pos_or_panic!(99.5));
+//! ```
+
+use crate::binary::{MAX_MANTISSA, from_parts};
+use crate::error::PositiveError;
+use crate::positive::Positive;
+use bson::{Bson, Decimal128};
+use core::str::FromStr;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// Bias added to the exponent in the `Decimal128` encoding.
+const EXPONENT_BIAS: i32 = 6176;
+
+/// Width of the coefficient field when the combination field does not
+/// start with `11`.
+const COEFFICIENT_BITS: u32 = 113;
+
+/// Largest canonical coefficient, `10^34 - 1`.
+const MAX_COEFFICIENT: u128 = 9_999_999_999_999_999_999_999_999_999_999_999;
+
+/// Largest `Decimal` scale.
+const MAX_SCALE: i32 = 28;
+
+impl From<Positive> for Decimal128 {
+    fn from(value: Positive) -> Self {
+        let value = value.to_dec();
+        let exponent = (EXPONENT_BIAS - value.scale() as i32) as u128;
+        let bits = (exponent << COEFFICIENT_BITS) | value.mantissa().unsigned_abs();
+        Decimal128::from_bytes(bits.to_le_bytes())
+    }
+}
+
+impl From<Positive> for Bson {
+    fn from(value: Positive) -> Self {
+        Bson::Decimal128(value.into())
+    }
+}
+
+impl TryFrom<Decimal128> for Positive {
+    type Error = PositiveError;
+
+    fn try_from(value: Decimal128) -> Result<Self, Self::Error> {
+        let bits = u128::from_le_bytes(value.bytes());
+        let negative = bits >> 127 == 1;
+        let (mut coefficient, mut exponent) = if (bits >> 125) & 0b11 == 0b11 {
+            match (bits >> 122) & 0b111 {
+                0b110 => return Err(error("infinite values are not supported")),
+                0b111 => return Err(error("NaN is not supported")),
+                // The coefficient would exceed 10^34 - 1: non-canonical, read
+                // as zero.
+                _ => (0, exponent_at(bits, COEFFICIENT_BITS - 2)),
+            }
+        } else {
+            let coefficient = bits & ((1 << COEFFICIENT_BITS) - 1);
+            let coefficient = if coefficient > MAX_COEFFICIENT {
+                0
+            } else {
+                coefficient
+            };
+            (coefficient, exponent_at(bits, COEFFICIENT_BITS))
+        };
+
+        if coefficient == 0 {
+            exponent = exponent.clamp(-MAX_SCALE, 0);
+        }
+        while exponent < 0
+            && (exponent < -MAX_SCALE || coefficient > MAX_MANTISSA)
+            && coefficient.is_multiple_of(10)
+        {
+            coefficient /= 10;
+            exponent += 1;
+        }
+        while exponent > 0 && coefficient <= MAX_MANTISSA {
+            coefficient *= 10;
+            exponent -= 1;
+        }
+        if exponent < -MAX_SCALE {
+            return Err(error("more than 28 decimal places"));
+        }
+        if exponent > 0 || coefficient > MAX_MANTISSA {
+            return Err(error("value out of range for Decimal"));
+        }
+
+        from_parts(coefficient, negative, (-exponent) as u32)
+    }
+}
+
+/// Reads the 14-bit biased exponent starting at bit `shift`.
+fn exponent_at(bits: u128, shift: u32) -> i32 {
+    ((bits >> shift) & 0x3fff) as i32 - EXPONENT_BIAS
+}
+
+fn error(reason: &str) -> PositiveError {
+    PositiveError::conversion_error("Decimal128", "Positive", reason)
+}
+
+/// Serializes a `Positive` as a BSON `Decimal128`.
+pub fn serialize<S>(value: &Positive, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    Decimal128::from(*value).serialize(serializer)
+}
+
+/// Deserializes a `Positive` from a BSON `Decimal128`, double, integer or
+/// string.
+pub fn deserialize<'de, D>(deserializer: D) -> Result<Positive, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let value = match Bson::deserialize(deserializer)? {
+        Bson::Decimal128(value) => Positive::try_from(value),
+        Bson::Double(value) => Positive::new(value),
+        Bson::Int32(value) => Positive::new_decimal(Decimal::from(value)),
+        Bson::Int64(value) => Positive::new_decimal(Decimal::from(value)),
+        Bson::String(value) => Positive::from_str(&value),
+        other => {
+            return Err(serde::de::Error::custom(format_args!(
+                "expected a BSON number or string, found {:?}",
+                other.element_type()
+            )));
+        }
+    };
+    value.map_err(crate::de::custom)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pos_or_panic;
+    use bson::doc;
+    use rust_decimal_macros::dec;
+    use serde::Deserialize;
+
+    fn parse(text: &str) -> Decimal128 {
+        text.parse().unwrap()
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Fill {
+        #[serde(with = "super")]
+        price: Positive,
+    }
+
+    #[test]
+    fn test_to_decimal128_is_exact() {
+        for text in ["1.25", "0.0001", "100", "1.2500", "0.000001"] {
+            let value = Positive::from_str(text).unwrap();
+            let encoded = Decimal128::from(value);
+            assert_eq!(encoded, parse(text));
+            assert_eq!(encoded.to_string(), text);
+        }
+        let tiny = Positive::from_str("0.0000000000000000000000000001").unwrap();
+        assert_eq!(Decimal128::from(tiny), parse("1E-28"));
+        let max = Decimal128::from(Positive::INFINITY);
+        assert_eq!(max.to_string(), Decimal::MAX.to_string());
+    }
+
+    #[test]
+    fn test_round_trip_keeps_scale() {
+        for value in [
+            pos_or_panic!(101.25),
+            Positive::new_decimal(dec!(7.000)).unwrap(),
+            Positive::INFINITY,
+        ] {
+            let back = Positive::try_from(Decimal128::from(value)).unwrap();
+            assert_eq!(back, value);
+            assert_eq!(back.to_dec().scale(), value.to_dec().scale());
+        }
+    }
+
+    #[test]
+    fn test_from_decimal128_normalizes() {
+        let value = Positive::try_from(parse("1.5E+3")).unwrap();
+        assert_eq!(value.to_dec(), dec!(1500));
+        let value = Positive::try_from(parse("2.50000000000000000000000000000000")).unwrap();
+        assert_eq!(value.to_dec(), dec!(2.5));
+        assert_eq!(value.to_dec().scale(), 28);
+        let zero = Positive::try_from(parse("-0E-40"));
+        if cfg!(feature = "non-zero") {
+            assert!(zero.is_err());
+        } else {
+            assert_eq!(zero.unwrap().to_dec(), Decimal::ZERO);
+        }
+    }
+
+    #[test]
+    fn test_from_decimal128_rejects_lossy_values() {
+        for text in [
+            "1.00000000000000000000000000001",
+            "1234567890123456789012345678901234",
+            "1E+29",
+            "NaN",
+            "Infinity",
+        ] {
+            let err = Positive::try_from(parse(text)).unwrap_err();
+            assert_eq!(err.code(), "conversion", "{text}");
+        }
+        let err = Positive::try_from(parse("-1.5")).unwrap_err();
+        assert_eq!(err.code(), "out_of_bounds");
+    }
+
+    #[test]
+    fn test_serde_adapter_writes_decimal128() {
+        let fill = Fill {
+            price: pos_or_panic!(12.5),
+        };
+        let document = bson::to_document(&fill).unwrap();
+        assert_eq!(document, doc! { "price": parse("12.5") });
+        let back: Fill = bson::from_document(document).unwrap();
+        assert_eq!(back, fill);
+
+        let bytes = bson::to_vec(&fill).unwrap();
+        let back: Fill = bson::from_slice(&bytes).unwrap();
+        assert_eq!(back, fill);
+    }
+
+    #[test]
+    fn test_serde_adapter_reads_legacy_values() {
+        for document in [
+            doc! { "price": 3.0 },
+            doc! { "price": 3_i32 },
+            doc! { "price": 3_i64 },
+            doc! { "price": "3" },
+        ] {
+            let fill: Fill = bson::from_document(document).unwrap();
+            assert_eq!(fill.price, pos_or_panic!(3.0));
+        }
+        assert!(bson::from_document::<Fill>(doc! { "price": -3.0 }).is_err());
+        assert!(bson::from_document::<Fill>(doc! { "price": true }).is_err());
+    }
+
+    #[test]
+    fn test_serde_adapter_extended_json() {
+        let fill = Fill {
+            price: pos_or_panic!(0.5),
+        };
+        let json = serde_json::to_string(&fill).unwrap();
+        assert_eq!(json, r#"{"price":{"$numberDecimal":"0.5"}}"#);
+        let back: Fill = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, fill);
+    }
+
+    #[test]
+    fn test_into_bson() {
+        let document = doc! { "qty": pos_or_panic!(2.0) };
+        assert!(matches!(document.get("qty"), Some(Bson::Decimal128(_))));
+    }
+}
@@ -0,0 +1,276 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 18/10/26
+******************************************************************************/
+
+//! Diesel integration for `Positive`.
+//!
+//! `Positive` derives `AsExpression` and `FromSqlRow`, so it can be used
+//! directly in `Insertable`, `AsChangeset` and `Queryable` structs, and
+//! implements `ToSql`/`FromSql` for:
+//!
+//! - `Numeric` on Postgres, delegating to the `Decimal` impls;
+//! - `Double` on SQLite (`diesel-sqlite`), going through `f64`;
+//! - `Text` on SQLite (`diesel-sqlite`), storing the exact `Decimal`
+//!   string.
+//!
+//! The `diesel` feature enables only Diesel's Postgres backend, which
+//! needs no client library; `diesel-sqlite` adds the SQLite backend.
+//!
+//! Reading a row that is not a valid `Positive` (a negative value, zero
+//! under the `non-zero` feature, unparsable text, ...) fails with a
+//! `DeserializationError` whose source chain contains the
+//! [`PositiveError`].
+//!
+//! ```rust
+//! # #[cfg(feature = "diesel-sqlite")]
+//! # fn main() {
+//! use diesel::prelude::*;
+//! use positive::{Positive, pos_or_panic};
+//!
+//! diesel::table! {
+//!     fills (id) {
+//!         id -> Integer,
+//!         price -> Text,
+//!     }
+//! }
+//!
+//! let mut conn = SqliteConnection::establish(":memory:").unwrap();
+//! diesel::sql_query("CREATE TABLE fills (id INTEGER PRIMARY KEY, price TEXT NOT NULL)")
+//!     .execute(&mut conn)
+//!     .unwrap();
+//! diesel::insert_into(fills::table)
+//!     .values((fills::id.eq(1), fills::price.eq(pos_or_panic!(101.25))))
+//!     .execute(&mut conn)
+//!     .unwrap();
+//!
+//! let price: Positive = fills::table.select(fills::price).first(&mut conn).unwrap();
+//! assert_eq!(price, pos_or_panic!(101.25));
+//! # }
+//! # #[cfg(not(feature = "diesel-sqlite"))]
+//! # fn main() {}
+//! ```
+
+use crate::error::PositiveError;
+use crate::positive::Positive;
+use diesel::deserialize::{self, FromSql};
+use diesel::pg::{Pg, PgValue};
+use diesel::serialize::{self, Output, ToSql};
+use diesel::sql_types::Numeric;
+use rust_decimal::Decimal;
+
+#[cfg(feature = "diesel-sqlite")]
+use alloc::string::{String, ToString};
+#[cfg(feature = "diesel-sqlite")]
+use core::str::FromStr;
+#[cfg(feature = "diesel-sqlite")]
+use diesel::serialize::IsNull;
+#[cfg(feature = "diesel-sqlite")]
+use diesel::sql_types::{Double, Text};
+#[cfg(feature = "diesel-sqlite")]
+use diesel::sqlite::{Sqlite, SqliteValue};
+
+impl ToSql<Numeric, Pg> for Positive {
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Pg>) -> serialize::Result {
+        let value = self.to_dec();
+        <Decimal as ToSql<Numeric, Pg>>::to_sql(&value, &mut out.reborrow())
+    }
+}
+
+impl FromSql<Numeric, Pg> for Positive {
+    fn from_sql(bytes: PgValue<'_>) -> deserialize::Result<Self> {
+        validate(Positive::new_decimal(
+            <Decimal as FromSql<Numeric, Pg>>::from_sql(bytes)?,
+        ))
+    }
+}
+
+#[cfg(feature = "diesel-sqlite")]
+impl ToSql<Double, Sqlite> for Positive {
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Sqlite>) -> serialize::Result {
+        let value = self.to_f64_checked().ok_or_else(|| {
+            PositiveError::conversion_error("Positive", "f64", "value not representable as f64")
+        })?;
+        out.set_value(value);
+        Ok(IsNull::No)
+    }
+}
+
+#[cfg(feature = "diesel-sqlite")]
+impl FromSql<Double, Sqlite> for Positive {
+    fn from_sql(value: SqliteValue<'_, '_, '_>) -> deserialize::Result<Self> {
+        validate(Positive::new(<f64 as FromSql<Double, Sqlite>>::from_sql(
+            value,
+        )?))
+    }
+}
+
+#[cfg(feature = "diesel-sqlite")]
+impl ToSql<Text, Sqlite> for Positive {
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Sqlite>) -> serialize::Result {
+        out.set_value(self.to_dec().to_string());
+        Ok(IsNull::No)
+    }
+}
+
+#[cfg(feature = "diesel-sqlite")]
+impl FromSql<Text, Sqlite> for Positive {
+    fn from_sql(value: SqliteValue<'_, '_, '_>) -> deserialize::Result<Self> {
+        let text = <String as FromSql<Text, Sqlite>>::from_sql(value)?;
+        validate(Positive::from_str(&text))
+    }
+}
+
+/// Boxes the `PositiveError` of an invalid row.
+fn validate(value: Result<Positive, PositiveError>) -> deserialize::Result<Positive> {
+    value.map_err(Into::into)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pos_or_panic;
+    use core::num::NonZeroU32;
+    #[cfg(feature = "diesel-sqlite")]
+    use diesel::prelude::*;
+    use diesel::sql_types::HasSqlType;
+    #[cfg(feature = "diesel-sqlite")]
+    use rust_decimal_macros::dec;
+
+    #[cfg(feature = "diesel-sqlite")]
+    diesel::table! {
+        quotes (id) {
+            id -> Integer,
+            bid -> Double,
+            ask -> Text,
+        }
+    }
+
+    #[cfg(feature = "diesel-sqlite")]
+    #[derive(Debug, Insertable, Queryable, PartialEq)]
+    #[diesel(table_name = quotes)]
+    struct Quote {
+        id: i32,
+        bid: Positive,
+        ask: Positive,
+    }
+
+    #[cfg(feature = "diesel-sqlite")]
+    /// Finds the `PositiveError` in the source chain of a Diesel error.
+    fn positive_error(err: &diesel::result::Error) -> &PositiveError {
+        let mut current: Option<&(dyn std::error::Error + 'static)> = Some(err);
+        while let Some(error) = current {
+            if let Some(found) = error.downcast_ref::<PositiveError>() {
+                return found;
+            }
+            current = error.source();
+        }
+        panic!("no PositiveError in {err:?}");
+    }
+
+    #[cfg(feature = "diesel-sqlite")]
+    fn connection() -> SqliteConnection {
+        let mut conn = SqliteConnection::establish(":memory:").unwrap();
+        diesel::sql_query(
+            "CREATE TABLE quotes (id INTEGER PRIMARY KEY, bid DOUBLE NOT NULL, ask TEXT NOT NULL)",
+        )
+        .execute(&mut conn)
+        .unwrap();
+        conn
+    }
+
+    #[cfg(feature = "diesel-sqlite")]
+    #[test]
+    fn test_sqlite_round_trip() {
+        let mut conn = connection();
+        let quote = Quote {
+            id: 1,
+            bid: Positive::new_decimal(dec!(99.5)).unwrap(),
+            ask: Positive::new_decimal(dec!(100.1250)).unwrap(),
+        };
+        diesel::insert_into(quotes::table)
+            .values(&quote)
+            .execute(&mut conn)
+            .unwrap();
+
+        let loaded: Quote = quotes::table.first(&mut conn).unwrap();
+        assert_eq!(loaded, quote);
+        assert_eq!(loaded.ask.to_dec().scale(), 4);
+    }
+
+    #[cfg(feature = "diesel-sqlite")]
+    #[test]
+    fn test_sqlite_rejects_invalid_rows() {
+        let mut conn = connection();
+        diesel::sql_query("INSERT INTO quotes VALUES (1, -1.5, '2'), (2, 1.5, 'abc')")
+            .execute(&mut conn)
+            .unwrap();
+
+        let bid = quotes::table
+            .filter(quotes::id.eq(1))
+            .select(quotes::bid)
+            .first::<Positive>(&mut conn)
+            .unwrap_err();
+        assert_eq!(positive_error(&bid).code(), "out_of_bounds");
+
+        let ask = quotes::table
+            .filter(quotes::id.eq(2))
+            .select(quotes::ask)
+            .first::<Positive>(&mut conn)
+            .unwrap_err();
+        assert_eq!(positive_error(&ask).code(), "parse");
+    }
+
+    #[cfg(feature = "diesel-sqlite")]
+    #[test]
+    fn test_nullable_columns() {
+        let mut conn = connection();
+        let value: Option<Positive> =
+            diesel::select(None::<Positive>.into_sql::<diesel::sql_types::Nullable<Text>>())
+                .get_result(&mut conn)
+                .unwrap();
+        assert_eq!(value, None);
+    }
+
+    /// Binary `NUMERIC` value: digit count, weight, sign and display
+    /// scale, followed by base-10000 digits.
+    fn pg_numeric(weight: i16, negative: bool, scale: u16, digits: &[u16]) -> Vec<u8> {
+        let sign: u16 = if negative { 0x4000 } else { 0 };
+        let header = [digits.len() as u16, weight as u16, sign, scale];
+        header
+            .iter()
+            .chain(digits)
+            .flat_map(|word| word.to_be_bytes())
+            .collect()
+    }
+
+    fn pg_from_sql(bytes: &[u8]) -> deserialize::Result<Positive> {
+        let numeric_oid = || NonZeroU32::new(1700).unwrap();
+        <Positive as FromSql<Numeric, Pg>>::from_sql(PgValue::new(bytes, &numeric_oid))
+    }
+
+    #[test]
+    fn test_pg_from_sql() {
+        let value = pg_from_sql(&pg_numeric(0, false, 2, &[3, 5000])).unwrap();
+        assert_eq!(value, pos_or_panic!(3.5));
+    }
+
+    #[test]
+    fn test_pg_rejects_negative_numeric() {
+        let err = pg_from_sql(&pg_numeric(0, true, 1, &[3, 5000])).unwrap_err();
+        let err = err.downcast_ref::<PositiveError>().unwrap();
+        assert_eq!(err.code(), "out_of_bounds");
+    }
+
+    #[test]
+    fn test_pg_numeric_type() {
+        fn assert_numeric<T>()
+        where
+            T: ToSql<Numeric, Pg> + FromSql<Numeric, Pg>,
+            Pg: HasSqlType<Numeric>,
+        {
+        }
+        assert_numeric::<Positive>();
+    }
+}
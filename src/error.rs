@@ -9,13 +9,98 @@
 //! This module provides error handling for operations involving positive decimal values,
 //! including validation, arithmetic operations, conversions, and precision issues.
 
-use thiserror::Error;
+use alloc::boxed::Box;
+use alloc::string::{String, ToString};
+use core::fmt;
+use rust_decimal::Decimal;
+
+/// The operation that produced an [`PositiveError::ArithmeticError`].
+///
+/// Operations on `f64` and `Decimal` operands report the same variant as
+/// the corresponding `Positive` operation. New variants may be added in
+/// minor releases.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub enum Operation {
+    /// Addition.
+    Add,
+    /// Subtraction.
+    Sub,
+    /// Multiplication.
+    Mul,
+    /// Division.
+    Div,
+    /// Square root.
+    Sqrt,
+    /// Natural logarithm.
+    Ln,
+    /// Base-10 logarithm.
+    Log10,
+    /// Exponential.
+    Exp,
+    /// Exponentiation.
+    Pow,
+    /// Rounding.
+    Round,
+    /// Percentage change (`Positive::pct_change`).
+    PctChange,
+    /// Logarithmic return (`Positive::log_return`).
+    LogReturn,
+    /// Arithmetic range (`Positive::range`, `Positive::range_inclusive`).
+    Range,
+    /// Logarithmic grid (`Positive::logspace`).
+    Logspace,
+    /// Geometric grid (`Positive::geomspace`).
+    Geomspace,
+    /// Grid centred on a value (`Positive::around`).
+    Around,
+    /// Snapping to a quantum (`ToleranceOrd::new`).
+    Quantize,
+}
+
+impl Operation {
+    /// Returns the stable lower-case name of the operation.
+    #[must_use]
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Operation::Add => "add",
+            Operation::Sub => "sub",
+            Operation::Mul => "mul",
+            Operation::Div => "div",
+            Operation::Sqrt => "sqrt",
+            Operation::Ln => "ln",
+            Operation::Log10 => "log10",
+            Operation::Exp => "exp",
+            Operation::Pow => "pow",
+            Operation::Round => "round",
+            Operation::PctChange => "pct_change",
+            Operation::LogReturn => "log_return",
+            Operation::Range => "range",
+            Operation::Logspace => "logspace",
+            Operation::Geomspace => "geomspace",
+            Operation::Around => "around",
+            Operation::Quantize => "quantize",
+        }
+    }
+}
+
+impl fmt::Display for Operation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
 
 /// Represents errors that can occur during positive decimal operations.
 ///
 /// This enum provides a structured way to handle various error conditions that may arise
 /// when working with positive decimal values, including validation, arithmetic operations,
-/// conversions, and precision issues.
+/// conversions, and precision issues. Values are reported as exact `Decimal`s, and each
+/// variant has a stable machine-readable [`code`](PositiveError::code).
+///
+/// The enum is `#[non_exhaustive]`: match on it with a wildcard arm.
 ///
 /// # Variants
 ///
@@ -24,17 +109,37 @@ use thiserror::Error;
 /// * `ConversionError` - Error when converting between types
 /// * `OutOfBounds` - Value exceeds defined limits
 /// * `InvalidPrecision` - Invalid decimal precision settings
+/// * `ParseError` - Text could not be parsed as a decimal
+/// * `ExpressionError` - A step of a `PositiveExpr` failed
+/// * `DeserializeError` - Deserialization failed at a known path
 /// * `Other` - Catch-all for other errors
-#[derive(Error, Debug)]
+///
+/// # Serialization
+///
+/// `PositiveError` serializes as an object tagged by its
+/// [`code`](PositiveError::code), with the variant's fields under `fields`
+/// and the `Display` text under `message`:
+///
+/// ```json
+/// {
+///   "code": "out_of_bounds",
+///   "fields": { "value": "-1", "min": "0", "max": "79228162514264337593543950335" },
+///   "message": "Value -1 is out of bounds (min: 0, max: 79228162514264337593543950335)"
+/// }
+/// ```
+///
+/// `Decimal` fields are strings, so values round-trip exactly. The
+/// `message` is ignored when deserializing.
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq)]
 pub enum PositiveError {
     /// Error when attempting to create a positive decimal from an invalid value.
     ///
-    /// Occurs when a value cannot be properly represented as a positive decimal,
-    /// such as when it's NaN, infinity, negative, or otherwise unsuitable.
-    #[error("Invalid positive value {value}: {reason}")]
+    /// Occurs when a value is a valid `Decimal` but otherwise unsuitable for
+    /// the requested operation.
     InvalidValue {
         /// The problematic value that caused the error.
-        value: f64,
+        value: Decimal,
         /// Detailed explanation of why the value is invalid.
         reason: String,
     },
@@ -44,20 +149,18 @@ pub enum PositiveError {
     /// Occurs during mathematical operations such as addition, subtraction,
     /// multiplication, or division when the operation cannot be completed
     /// correctly (e.g., division by zero, overflow, result would be negative).
-    #[error("Arithmetic error during {operation}: {reason}")]
     ArithmeticError {
-        /// The operation that failed (e.g., "subtraction", "division").
-        operation: String,
+        /// The operation that failed.
+        operation: Operation,
         /// Detailed explanation of why the operation failed.
         reason: String,
     },
 
     /// Error when converting between decimal types.
     ///
-    /// Occurs when a decimal value cannot be correctly converted from one
-    /// representation to another, such as between different precision levels
-    /// or between different decimal formats.
-    #[error("Failed to convert from {from_type} to {to_type}: {reason}")]
+    /// Occurs when a value cannot be correctly converted from one
+    /// representation to another, such as an `f64` NaN or infinity that has
+    /// no `Decimal` representation.
     ConversionError {
         /// The source type being converted from.
         from_type: String,
@@ -69,23 +172,22 @@ pub enum PositiveError {
 
     /// Error when a decimal value exceeds its bounds.
     ///
-    /// Occurs when a decimal value falls outside of acceptable minimum
-    /// or maximum values for a specific calculation context.
-    #[error("Value {value} is out of bounds (min: {min}, max: {max})")]
+    /// Occurs when a value falls outside of the acceptable range, most
+    /// commonly a negative value (or zero under the `non-zero` feature)
+    /// passed where a `Positive` is required.
     OutOfBounds {
         /// The value that is out of bounds.
-        value: f64,
+        value: Decimal,
         /// The minimum acceptable value.
-        min: f64,
+        min: Decimal,
         /// The maximum acceptable value.
-        max: f64,
+        max: Decimal,
     },
 
     /// Error when decimal precision is invalid.
     ///
     /// Occurs when an operation specifies or results in an invalid precision
     /// level that cannot be properly handled.
-    #[error("Invalid precision {precision}: {reason}")]
     InvalidPrecision {
         /// The problematic precision value.
         precision: i32,
@@ -93,11 +195,101 @@ pub enum PositiveError {
         reason: String,
     },
 
+    /// Error when text cannot be parsed as a `Decimal`.
+    ///
+    /// The underlying `rust_decimal` error is available through
+    /// [`core::error::Error::source`] with the `std` feature, where it
+    /// implements `Error`.
+    ParseError {
+        /// The rejected input.
+        input: String,
+        /// The underlying parse error.
+        source: rust_decimal::Error,
+    },
+
+    /// Error raised by a step of a `PositiveExpr`.
+    ///
+    /// The error of the failing step is available through
+    /// [`core::error::Error::source`].
+    ExpressionError {
+        /// The operations applied up to and including the failing one,
+        /// joined with `" -> "`.
+        chain: String,
+        /// The error of the failing step.
+        source: Box<PositiveError>,
+    },
+
+    /// Error when deserializing a value, with the path at which it failed.
+    ///
+    /// Produced by [`crate::de::deserialize_with_path`]. When the failure
+    /// came from a `Positive` field, the structured error is available as
+    /// `source`.
+    DeserializeError {
+        /// Path to the failing value (e.g. `orders[2].price`), or `.` for
+        /// the root.
+        path: String,
+        /// The deserializer's error message.
+        message: String,
+        /// The structured error raised by `Positive`, if any.
+        source: Option<Box<PositiveError>>,
+    },
+
     /// Catch-all error for other positive decimal errors.
-    #[error("Positive error: {0}")]
     Other(String),
 }
 
+impl fmt::Display for PositiveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PositiveError::InvalidValue { value, reason } => {
+                write!(f, "Invalid positive value {value}: {reason}")
+            }
+            PositiveError::ArithmeticError { operation, reason } => {
+                write!(f, "Arithmetic error during {operation}: {reason}")
+            }
+            PositiveError::ConversionError {
+                from_type,
+                to_type,
+                reason,
+            } => write!(
+                f,
+                "Failed to convert from {from_type} to {to_type}: {reason}"
+            ),
+            PositiveError::OutOfBounds { value, min, max } => {
+                write!(f, "Value {value} is out of bounds (min: {min}, max: {max})")
+            }
+            PositiveError::InvalidPrecision { precision, reason } => {
+                write!(f, "Invalid precision {precision}: {reason}")
+            }
+            PositiveError::ParseError { input, .. } => {
+                write!(f, "Failed to parse {input:?} as Decimal")
+            }
+            PositiveError::ExpressionError { chain, source } => {
+                write!(f, "Expression failed at {chain}: {source}")
+            }
+            PositiveError::DeserializeError { path, message, .. } => {
+                write!(f, "Failed to deserialize at {path}: {message}")
+            }
+            PositiveError::Other(reason) => write!(f, "Positive error: {reason}"),
+        }
+    }
+}
+
+impl core::error::Error for PositiveError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            #[cfg(feature = "std")]
+            PositiveError::ParseError { source, .. } => Some(source),
+            PositiveError::ExpressionError { source, .. } => Some(source.as_ref()),
+            PositiveError::DeserializeError {
+                source: Some(source),
+                ..
+            } => Some(source.as_ref()),
+            _ => None,
+        }
+    }
+}
+
 /// A specialized `Result` type for positive decimal operations.
 ///
 /// This type alias provides a convenient shorthand for operations that can result in a
@@ -113,7 +305,7 @@ impl PositiveError {
     ///
     /// # Arguments
     ///
-    /// * `value` - The problematic floating-point value
+    /// * `value` - The problematic value
     /// * `reason` - Explanation of why the value is invalid
     ///
     /// # Returns
@@ -122,7 +314,7 @@ impl PositiveError {
     #[cold]
     #[inline(never)]
     #[must_use]
-    pub fn invalid_value(value: f64, reason: &str) -> Self {
+    pub fn invalid_value(value: Decimal, reason: &str) -> Self {
         PositiveError::InvalidValue {
             value,
             reason: reason.to_string(),
@@ -133,7 +325,7 @@ impl PositiveError {
     ///
     /// # Arguments
     ///
-    /// * `operation` - The name of the operation that failed
+    /// * `operation` - The operation that failed
     /// * `reason` - Explanation of why the operation failed
     ///
     /// # Returns
@@ -142,9 +334,9 @@ impl PositiveError {
     #[cold]
     #[inline(never)]
     #[must_use]
-    pub fn arithmetic_error(operation: &str, reason: &str) -> Self {
+    pub fn arithmetic_error(operation: Operation, reason: &str) -> Self {
         PositiveError::ArithmeticError {
-            operation: operation.to_string(),
+            operation,
             reason: reason.to_string(),
         }
     }
@@ -175,7 +367,7 @@ impl PositiveError {
     ///
     /// # Arguments
     ///
-    /// * `value` - The out-of-bounds floating-point value
+    /// * `value` - The out-of-bounds value
     /// * `min` - The lower bound (inclusive) of the valid range
     /// * `max` - The upper bound (inclusive) of the valid range
     ///
@@ -185,7 +377,7 @@ impl PositiveError {
     #[cold]
     #[inline(never)]
     #[must_use]
-    pub fn out_of_bounds(value: f64, min: f64, max: f64) -> Self {
+    pub fn out_of_bounds(value: Decimal, min: Decimal, max: Decimal) -> Self {
         PositiveError::OutOfBounds { value, min, max }
     }
 
@@ -208,6 +400,279 @@ impl PositiveError {
             reason: reason.to_string(),
         }
     }
+
+    /// Creates a new `ParseError` error.
+    ///
+    /// # Arguments
+    ///
+    /// * `input` - The rejected input
+    /// * `source` - The underlying `rust_decimal` error
+    ///
+    /// # Returns
+    ///
+    /// A new `PositiveError::ParseError` instance
+    #[cold]
+    #[inline(never)]
+    #[must_use]
+    pub fn parse_error(input: &str, source: rust_decimal::Error) -> Self {
+        PositiveError::ParseError {
+            input: input.to_string(),
+            source,
+        }
+    }
+
+    /// Returns a stable, machine-readable code identifying the variant.
+    ///
+    /// Codes never change once published, so they are safe to log, match
+    /// on in other languages, or return from an API.
+    ///
+    /// | Variant            | Code                |
+    /// |--------------------|---------------------|
+    /// | `InvalidValue`     | `invalid_value`     |
+    /// | `ArithmeticError`  | `arithmetic`        |
+    /// | `ConversionError`  | `conversion`        |
+    /// | `OutOfBounds`      | `out_of_bounds`     |
+    /// | `InvalidPrecision` | `invalid_precision` |
+    /// | `ParseError`       | `parse`             |
+    /// | `ExpressionError`  | `expression`        |
+    /// | `DeserializeError` | `deserialize`       |
+    /// | `Other`            | `other`             |
+    #[must_use]
+    pub const fn code(&self) -> &'static str {
+        match self {
+            PositiveError::InvalidValue { .. } => "invalid_value",
+            PositiveError::ArithmeticError { .. } => "arithmetic",
+            PositiveError::ConversionError { .. } => "conversion",
+            PositiveError::OutOfBounds { .. } => "out_of_bounds",
+            PositiveError::InvalidPrecision { .. } => "invalid_precision",
+            PositiveError::ParseError { .. } => "parse",
+            PositiveError::ExpressionError { .. } => "expression",
+            PositiveError::DeserializeError { .. } => "deserialize",
+            PositiveError::Other(_) => "other",
+        }
+    }
+
+    /// Returns the innermost error, following `ExpressionError` and
+    /// `DeserializeError` wrappers.
+    #[must_use]
+    pub fn root_cause(&self) -> &PositiveError {
+        match self {
+            PositiveError::ExpressionError { source, .. }
+            | PositiveError::DeserializeError {
+                source: Some(source),
+                ..
+            } => source.root_cause(),
+            other => other,
+        }
+    }
+}
+
+/// `serde` (and `utoipa`) support for [`PositiveError`], going through a
+/// private wire representation.
+#[cfg(feature = "serde")]
+mod wire {
+    use super::{Operation, PositiveError};
+    use alloc::boxed::Box;
+    use alloc::string::{String, ToString};
+    use rust_decimal::Decimal;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    /// Wire representation of [`PositiveError`].
+    #[derive(Serialize, Deserialize)]
+    #[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+    #[cfg_attr(feature = "utoipa", schema(as = PositiveError))]
+    struct ErrorRepr {
+        #[serde(flatten)]
+        detail: ErrorDetail,
+        /// Human-readable description; ignored when deserializing.
+        #[serde(default)]
+        message: String,
+    }
+
+    /// Variant fields of a `PositiveError`, tagged by its code.
+    #[derive(Serialize, Deserialize)]
+    #[serde(tag = "code", content = "fields", rename_all = "snake_case")]
+    #[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+    #[cfg_attr(feature = "utoipa", schema(as = PositiveErrorDetail))]
+    enum ErrorDetail {
+        InvalidValue {
+            value: Decimal,
+            reason: String,
+        },
+        Arithmetic {
+            operation: Operation,
+            reason: String,
+        },
+        Conversion {
+            from_type: String,
+            to_type: String,
+            reason: String,
+        },
+        OutOfBounds {
+            value: Decimal,
+            min: Decimal,
+            max: Decimal,
+        },
+        InvalidPrecision {
+            precision: i32,
+            reason: String,
+        },
+        Parse {
+            input: String,
+            reason: String,
+        },
+        Expression {
+            chain: String,
+            #[cfg_attr(feature = "utoipa", schema(no_recursion))]
+            source: Box<PositiveError>,
+        },
+        Deserialize {
+            path: String,
+            message: String,
+            #[cfg_attr(feature = "utoipa", schema(no_recursion))]
+            source: Option<Box<PositiveError>>,
+        },
+        Other {
+            reason: String,
+        },
+    }
+
+    impl From<PositiveError> for ErrorDetail {
+        fn from(error: PositiveError) -> Self {
+            match error {
+                PositiveError::InvalidValue { value, reason } => {
+                    ErrorDetail::InvalidValue { value, reason }
+                }
+                PositiveError::ArithmeticError { operation, reason } => {
+                    ErrorDetail::Arithmetic { operation, reason }
+                }
+                PositiveError::ConversionError {
+                    from_type,
+                    to_type,
+                    reason,
+                } => ErrorDetail::Conversion {
+                    from_type,
+                    to_type,
+                    reason,
+                },
+                PositiveError::OutOfBounds { value, min, max } => {
+                    ErrorDetail::OutOfBounds { value, min, max }
+                }
+                PositiveError::InvalidPrecision { precision, reason } => {
+                    ErrorDetail::InvalidPrecision { precision, reason }
+                }
+                PositiveError::ParseError { input, source } => ErrorDetail::Parse {
+                    input,
+                    reason: source.to_string(),
+                },
+                PositiveError::ExpressionError { chain, source } => {
+                    ErrorDetail::Expression { chain, source }
+                }
+                PositiveError::DeserializeError {
+                    path,
+                    message,
+                    source,
+                } => ErrorDetail::Deserialize {
+                    path,
+                    message,
+                    source,
+                },
+                PositiveError::Other(reason) => ErrorDetail::Other { reason },
+            }
+        }
+    }
+
+    impl From<ErrorDetail> for PositiveError {
+        fn from(detail: ErrorDetail) -> Self {
+            match detail {
+                ErrorDetail::InvalidValue { value, reason } => {
+                    PositiveError::InvalidValue { value, reason }
+                }
+                ErrorDetail::Arithmetic { operation, reason } => {
+                    PositiveError::ArithmeticError { operation, reason }
+                }
+                ErrorDetail::Conversion {
+                    from_type,
+                    to_type,
+                    reason,
+                } => PositiveError::ConversionError {
+                    from_type,
+                    to_type,
+                    reason,
+                },
+                ErrorDetail::OutOfBounds { value, min, max } => {
+                    PositiveError::OutOfBounds { value, min, max }
+                }
+                ErrorDetail::InvalidPrecision { precision, reason } => {
+                    PositiveError::InvalidPrecision { precision, reason }
+                }
+                // The original `rust_decimal::Error` is not serializable; its
+                // message is carried over verbatim.
+                ErrorDetail::Parse { input, reason } => PositiveError::ParseError {
+                    input,
+                    source: rust_decimal::Error::ErrorString(reason),
+                },
+                ErrorDetail::Expression { chain, source } => {
+                    PositiveError::ExpressionError { chain, source }
+                }
+                ErrorDetail::Deserialize {
+                    path,
+                    message,
+                    source,
+                } => PositiveError::DeserializeError {
+                    path,
+                    message,
+                    source,
+                },
+                ErrorDetail::Other { reason } => PositiveError::Other(reason),
+            }
+        }
+    }
+
+    impl Serialize for PositiveError {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            ErrorRepr {
+                message: self.to_string(),
+                detail: self.clone().into(),
+            }
+            .serialize(serializer)
+        }
+    }
+
+    impl<'de> Deserialize<'de> for PositiveError {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            ErrorRepr::deserialize(deserializer).map(|repr| repr.detail.into())
+        }
+    }
+
+    #[cfg(feature = "utoipa")]
+    impl utoipa::PartialSchema for PositiveError {
+        fn schema() -> utoipa::openapi::RefOr<utoipa::openapi::schema::Schema> {
+            <ErrorRepr as utoipa::PartialSchema>::schema()
+        }
+    }
+
+    #[cfg(feature = "utoipa")]
+    impl utoipa::ToSchema for PositiveError {
+        fn name() -> std::borrow::Cow<'static, str> {
+            std::borrow::Cow::Borrowed("PositiveError")
+        }
+
+        fn schemas(
+            schemas: &mut Vec<(
+                String,
+                utoipa::openapi::RefOr<utoipa::openapi::schema::Schema>,
+            )>,
+        ) {
+            <ErrorRepr as utoipa::ToSchema>::schemas(schemas);
+        }
+    }
 }
 
 impl From<&str> for PositiveError {
@@ -232,14 +697,14 @@ mod tests {
 
     #[test]
     fn test_invalid_value_error() {
-        let error = PositiveError::invalid_value(-1.0, "Value cannot be negative");
+        let error = PositiveError::invalid_value(Decimal::NEGATIVE_ONE, "Value cannot be negative");
         assert!(matches!(error, PositiveError::InvalidValue { .. }));
         assert!(error.to_string().contains("cannot be negative"));
     }
 
     #[test]
     fn test_arithmetic_error() {
-        let error = PositiveError::arithmetic_error("subtraction", "Result would be negative");
+        let error = PositiveError::arithmetic_error(Operation::Sub, "Result would be negative");
         assert!(matches!(error, PositiveError::ArithmeticError { .. }));
         assert!(error.to_string().contains("would be negative"));
     }
@@ -253,7 +718,8 @@ mod tests {
 
     #[test]
     fn test_out_of_bounds_error() {
-        let error = PositiveError::out_of_bounds(-5.0, 0.0, 100.0);
+        let error =
+            PositiveError::out_of_bounds(Decimal::from(-5), Decimal::ZERO, Decimal::ONE_HUNDRED);
         assert!(matches!(error, PositiveError::OutOfBounds { .. }));
         assert!(error.to_string().contains("-5"));
     }
@@ -278,4 +744,110 @@ mod tests {
         assert!(matches!(error, PositiveError::Other(_)));
         assert!(error.to_string().contains("Another error"));
     }
+
+    #[test]
+    fn test_out_of_bounds_keeps_exact_decimal() {
+        let value = Decimal::new(-1, 19);
+        let error = PositiveError::out_of_bounds(value, Decimal::ZERO, Decimal::MAX);
+        assert!(matches!(error, PositiveError::OutOfBounds { value: v, .. } if v == value));
+        assert!(error.to_string().contains("-0.0000000000000000001"));
+    }
+
+    #[test]
+    fn test_codes() {
+        let error = PositiveError::arithmetic_error(Operation::Div, "division by zero");
+        assert_eq!(error.code(), "arithmetic");
+        assert_eq!(PositiveError::from("x").code(), "other");
+        assert_eq!(
+            PositiveError::invalid_precision(29, "too many").code(),
+            "invalid_precision"
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_parse_error_source() {
+        use std::error::Error as _;
+        let source = "abc".parse::<Decimal>().unwrap_err();
+        let error = PositiveError::parse_error("abc", source);
+        assert_eq!(error.code(), "parse");
+        assert!(error.source().is_some());
+    }
+
+    #[test]
+    fn test_expression_error_root_cause() {
+        use core::error::Error as _;
+        let error = PositiveError::ExpressionError {
+            chain: "add -> sub".to_string(),
+            source: Box::new(PositiveError::arithmetic_error(Operation::Sub, "negative")),
+        };
+        assert_eq!(error.code(), "expression");
+        assert!(error.source().is_some());
+        assert_eq!(error.root_cause().code(), "arithmetic");
+        assert_eq!(Operation::Sub.to_string(), "sub");
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serialize_shape() {
+        let error =
+            PositiveError::out_of_bounds(Decimal::NEGATIVE_ONE, Decimal::ZERO, Decimal::TEN);
+        let json = serde_json::to_value(&error).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "code": "out_of_bounds",
+                "fields": { "value": "-1", "min": "0", "max": "10" },
+                "message": "Value -1 is out of bounds (min: 0, max: 10)",
+            })
+        );
+        assert_eq!(json["code"], error.code());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_round_trip() {
+        let errors = [
+            PositiveError::invalid_value(Decimal::new(-1, 19), "negative"),
+            PositiveError::arithmetic_error(Operation::PctChange, "division by zero"),
+            PositiveError::conversion_error("f64", "Decimal", "NaN"),
+            PositiveError::invalid_precision(29, "too many"),
+            PositiveError::parse_error("abc", "abc".parse::<Decimal>().unwrap_err()),
+            PositiveError::ExpressionError {
+                chain: "add -> sub".to_string(),
+                source: Box::new(PositiveError::out_of_bounds(
+                    Decimal::NEGATIVE_ONE,
+                    Decimal::ZERO,
+                    Decimal::MAX,
+                )),
+            },
+            PositiveError::DeserializeError {
+                path: "legs[1]".to_string(),
+                message: "missing field".to_string(),
+                source: None,
+            },
+            PositiveError::from("other"),
+        ];
+        for error in errors {
+            let json = serde_json::to_string(&error).unwrap();
+            let back: PositiveError = serde_json::from_str(&json).unwrap();
+            assert_eq!(back.code(), error.code());
+            assert_eq!(back.to_string(), error.to_string());
+        }
+    }
+
+    #[cfg(feature = "utoipa")]
+    #[test]
+    fn test_utoipa_schema() {
+        use utoipa::ToSchema;
+        let mut schemas = Vec::new();
+        PositiveError::schemas(&mut schemas);
+        assert_eq!(PositiveError::name(), "PositiveError");
+        let detail = serde_json::to_string(&schemas).unwrap();
+        assert!(detail.contains("PositiveErrorDetail"));
+        assert!(detail.contains("out_of_bounds"));
+        let schema =
+            serde_json::to_string(&<PositiveError as utoipa::PartialSchema>::schema()).unwrap();
+        assert!(schema.contains("message"));
+    }
 }
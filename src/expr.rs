@@ -0,0 +1,936 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 18/10/26
+******************************************************************************/
+
+//! Formulas over named `Positive` variables.
+//!
+//! [`Formula::parse`] compiles a formula such as
+//! `max(notional * 0.0004, 1.5)` once; [`Formula::eval`] then evaluates it
+//! against any [`Variables`] source (a map, a slice of pairs or a
+//! closure), and [`Formula::eval_values`] against values given in the
+//! order of [`Formula::variables`], without looking names up.
+//!
+//! The syntax has decimal literals, variables (`[A-Za-z_][A-Za-z0-9_]*`),
+//! `+`, `-`, `*` and `/` with the usual precedence, parentheses and the
+//! functions `min(a, b, ...)`, `max(a, b, ...)`, `sqrt(x)`, `ln(x)`,
+//! `round_to(x, places)` and `clamp(x, lo, hi)`. Every step uses checked
+//! arithmetic, and division rounds with
+//! [`DIV_ROUNDING_STRATEGY`](crate::DIV_ROUNDING_STRATEGY) like the `/`
+//! operator on `Positive`.
+//!
+//! Parsing and evaluation recurse into parentheses and calls, so their
+//! nesting is limited to [`MAX_DEPTH`] levels. Chains of operators such as
+//! `a + b - c` are evaluated in a loop and may be of any length.
+//!
+//! Errors carry the byte range of the offending part of the formula, both
+//! when parsing and when a step fails during evaluation.
+//!
+//! ```rust
+//! use positive::expr::Formula;
+//! use positive::pos_or_panic;
+//!
+//! let fee = Formula::parse("max(notional * 0.0004, 1.5)").unwrap();
+//! assert_eq!(fee.variables().collect::<Vec<_>>(), ["notional"]);
+//!
+//! let small = fee.eval(&[("notional", pos_or_panic!(1_000.0))]).unwrap();
+//! assert_eq!(small, pos_or_panic!(1.5));
+//! let large = fee.eval_values(&[pos_or_panic!(25_000.0)]).unwrap();
+//! assert_eq!(large, pos_or_panic!(10.0));
+//!
+//! let margin = Formula::parse("qty * (price - strike)").unwrap();
+//! let position = [
+//!     ("qty", pos_or_panic!(2.0)),
+//!     ("price", pos_or_panic!(95.0)),
+//!     ("strike", pos_or_panic!(100.0)),
+//! ];
+//! let err = margin.eval(&position).unwrap_err();
+//! assert_eq!(err.span, 6..22);
+//! assert_eq!(err.kind.error().unwrap().code(), "out_of_bounds");
+//! ```
+
+use crate::error::{Operation, PositiveError, PositiveResult};
+use crate::positive::{Positive, round_div};
+use alloc::boxed::Box;
+use alloc::collections::BTreeMap;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::borrow::Borrow;
+use core::fmt;
+use core::ops::Range;
+use core::str::FromStr;
+use rust_decimal::{Decimal, MathematicalOps};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+#[cfg(feature = "std")]
+use std::collections::HashMap;
+#[cfg(feature = "std")]
+use std::hash::{BuildHasher, Hash};
+
+/// Deepest nesting accepted by [`Formula::parse`].
+pub const MAX_DEPTH: usize = 128;
+
+/// What went wrong in an [`ExprError`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum ExprErrorKind {
+    /// The text is not a well-formed formula.
+    Syntax(String),
+    /// A call names a function that does not exist.
+    UnknownFunction(String),
+    /// A function was called with the wrong number of arguments.
+    ArgumentCount {
+        /// The function name.
+        function: &'static str,
+        /// The accepted number of arguments, such as `2 arguments`.
+        expected: &'static str,
+        /// The number of arguments given.
+        found: usize,
+    },
+    /// A variable has no value.
+    UnknownVariable(String),
+    /// [`Formula::eval_values`] received the wrong number of values.
+    ValueCount {
+        /// The number of variables of the formula.
+        expected: usize,
+        /// The number of values given.
+        found: usize,
+    },
+    /// A literal is not a valid `Positive`, or a step of the evaluation
+    /// failed.
+    Positive(PositiveError),
+}
+
+impl ExprErrorKind {
+    /// Returns the `PositiveError` of a `Positive` error.
+    #[must_use]
+    pub fn error(&self) -> Option<&PositiveError> {
+        match self {
+            ExprErrorKind::Positive(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for ExprErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExprErrorKind::Syntax(message) => f.write_str(message),
+            ExprErrorKind::UnknownFunction(name) => write!(f, "unknown function `{name}`"),
+            ExprErrorKind::ArgumentCount {
+                function,
+                expected,
+                found,
+            } => write!(f, "`{function}` takes {expected}, found {found}"),
+            ExprErrorKind::UnknownVariable(name) => write!(f, "unknown variable `{name}`"),
+            ExprErrorKind::ValueCount { expected, found } => {
+                write!(f, "expected {expected} values, found {found}")
+            }
+            ExprErrorKind::Positive(error) => write!(f, "{error}"),
+        }
+    }
+}
+
+/// An error located in the source of a formula.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ExprError {
+    /// The byte range of the offending part of the formula.
+    pub span: Range<usize>,
+    /// What went wrong.
+    pub kind: ExprErrorKind,
+}
+
+impl ExprError {
+    fn new(span: Range<usize>, kind: ExprErrorKind) -> Self {
+        ExprError { span, kind }
+    }
+
+    fn syntax(span: Range<usize>, message: impl Into<String>) -> Self {
+        ExprError::new(span, ExprErrorKind::Syntax(message.into()))
+    }
+}
+
+impl fmt::Display for ExprError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at {}..{}", self.kind, self.span.start, self.span.end)
+    }
+}
+
+impl core::error::Error for ExprError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        self.kind
+            .error()
+            .map(|error| error as &(dyn core::error::Error + 'static))
+    }
+}
+
+/// A source of variable values for [`Formula::eval`].
+pub trait Variables {
+    /// Returns the value of `name`, or `None` if it is not defined.
+    fn get(&self, name: &str) -> Option<Positive>;
+}
+
+impl<K: Borrow<str> + Ord> Variables for BTreeMap<K, Positive> {
+    fn get(&self, name: &str) -> Option<Positive> {
+        BTreeMap::get(self, name).copied()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<K: Borrow<str> + Eq + Hash, S: BuildHasher> Variables for HashMap<K, Positive, S> {
+    fn get(&self, name: &str) -> Option<Positive> {
+        HashMap::get(self, name).copied()
+    }
+}
+
+impl<K: AsRef<str>> Variables for [(K, Positive)] {
+    fn get(&self, name: &str) -> Option<Positive> {
+        self.iter()
+            .find(|(key, _)| key.as_ref() == name)
+            .map(|(_, value)| *value)
+    }
+}
+
+impl<K: AsRef<str>, const N: usize> Variables for [(K, Positive); N] {
+    fn get(&self, name: &str) -> Option<Positive> {
+        Variables::get(self.as_slice(), name)
+    }
+}
+
+impl<K: AsRef<str>> Variables for Vec<(K, Positive)> {
+    fn get(&self, name: &str) -> Option<Positive> {
+        Variables::get(self.as_slice(), name)
+    }
+}
+
+impl<F: Fn(&str) -> Option<Positive>> Variables for F {
+    fn get(&self, name: &str) -> Option<Positive> {
+        self(name)
+    }
+}
+
+/// A compiled formula.
+///
+/// Parse once with [`Formula::parse`] (or `str::parse`) and evaluate as
+/// often as needed. With the `serde` feature a formula serializes as its
+/// source text and is compiled when deserialized, so invalid formulas are
+/// rejected when the configuration is loaded.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Formula {
+    source: String,
+    root: Node,
+    /// Variable names in order of first use, with the span of that use.
+    variables: Vec<(String, Range<usize>)>,
+}
+
+impl Formula {
+    /// Compiles `source`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `ExprError` spanning the offending text for a syntax
+    /// error, nesting deeper than [`MAX_DEPTH`], an unknown function, a
+    /// wrong number of arguments or an invalid literal.
+    pub fn parse(source: &str) -> Result<Formula, ExprError> {
+        let mut parser = Parser {
+            tokens: tokenize(source)?,
+            position: 0,
+            depth: 0,
+            variables: Vec::new(),
+        };
+        let root = parser.expression()?;
+        let token = parser.peek();
+        if token.kind != TokenKind::End {
+            return Err(ExprError::syntax(
+                token.span.clone(),
+                "expected an operator or the end of the formula",
+            ));
+        }
+        Ok(Formula {
+            source: source.to_string(),
+            root,
+            variables: parser.variables,
+        })
+    }
+
+    /// The source text of the formula.
+    #[must_use]
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    /// The names of the variables, in order of first use.
+    pub fn variables(&self) -> impl ExactSizeIterator<Item = &str> {
+        self.variables.iter().map(|(name, _)| name.as_str())
+    }
+
+    /// Evaluates the formula, looking each variable up in `variables`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `UnknownVariable` error at the first use of a variable
+    /// that `variables` does not define, or a `Positive` error at the
+    /// step that failed.
+    pub fn eval<V: Variables + ?Sized>(&self, variables: &V) -> Result<Positive, ExprError> {
+        self.root.eval(&|index| {
+            let (name, span) = &self.variables[index];
+            variables.get(name).ok_or_else(|| {
+                ExprError::new(span.clone(), ExprErrorKind::UnknownVariable(name.clone()))
+            })
+        })
+    }
+
+    /// Evaluates the formula with `values` given in the order of
+    /// [`Formula::variables`].
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ValueCount` error spanning the whole formula if the
+    /// number of values does not match, or a `Positive` error at the step
+    /// that failed.
+    pub fn eval_values(&self, values: &[Positive]) -> Result<Positive, ExprError> {
+        if values.len() != self.variables.len() {
+            return Err(ExprError::new(
+                0..self.source.len(),
+                ExprErrorKind::ValueCount {
+                    expected: self.variables.len(),
+                    found: values.len(),
+                },
+            ));
+        }
+        self.root.eval(&|index| Ok(values[index]))
+    }
+}
+
+impl FromStr for Formula {
+    type Err = ExprError;
+
+    fn from_str(source: &str) -> Result<Self, Self::Err> {
+        Formula::parse(source)
+    }
+}
+
+impl fmt::Display for Formula {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.source)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for Formula {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&self.source)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for Formula {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let source = String::deserialize(deserializer)?;
+        Formula::parse(&source).map_err(serde::de::Error::custom)
+    }
+}
+
+/// A node of the compiled formula with the span it was parsed from.
+#[derive(Clone, Debug, PartialEq)]
+struct Node {
+    kind: NodeKind,
+    span: Range<usize>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum NodeKind {
+    Literal(Positive),
+    /// Index into `Formula::variables`.
+    Variable(usize),
+    /// A left-associative chain of operators of the same precedence,
+    /// such as `a - b + c`.
+    Chain(Box<Node>, Vec<(BinaryOp, Node)>),
+    Call(Function, Vec<Node>),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum BinaryOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Function {
+    Min,
+    Max,
+    Sqrt,
+    Ln,
+    RoundTo,
+    Clamp,
+}
+
+impl Function {
+    fn from_name(name: &str) -> Option<Function> {
+        Some(match name {
+            "min" => Function::Min,
+            "max" => Function::Max,
+            "sqrt" => Function::Sqrt,
+            "ln" => Function::Ln,
+            "round_to" => Function::RoundTo,
+            "clamp" => Function::Clamp,
+            _ => return None,
+        })
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Function::Min => "min",
+            Function::Max => "max",
+            Function::Sqrt => "sqrt",
+            Function::Ln => "ln",
+            Function::RoundTo => "round_to",
+            Function::Clamp => "clamp",
+        }
+    }
+
+    /// The accepted number of arguments, as a range and as text.
+    fn arity(self) -> (Range<usize>, &'static str) {
+        match self {
+            Function::Min | Function::Max => (2..usize::MAX, "at least 2 arguments"),
+            Function::Sqrt | Function::Ln => (1..2, "1 argument"),
+            Function::RoundTo => (2..3, "2 arguments"),
+            Function::Clamp => (3..4, "3 arguments"),
+        }
+    }
+
+    fn apply(self, args: &[Positive]) -> PositiveResult<Positive> {
+        match (self, args) {
+            (Function::Min, _) => Ok(args.iter().copied().fold(args[0], Positive::min)),
+            (Function::Max, _) => Ok(args.iter().copied().fold(args[0], Positive::max)),
+            (Function::Sqrt, [x]) => x.sqrt_checked(),
+            (Function::Ln, [x]) => {
+                let value = x.to_dec().checked_ln().ok_or_else(|| {
+                    PositiveError::arithmetic_error(Operation::Ln, "logarithm of zero")
+                })?;
+                Positive::new_decimal(value)
+            }
+            (Function::RoundTo, [x, places]) => {
+                let places = places.to_dec();
+                if !places.fract().is_zero() {
+                    return Err(PositiveError::invalid_value(
+                        places,
+                        "decimal places must be a whole number",
+                    ));
+                }
+                let places = u32::try_from(places.normalize().mantissa()).unwrap_or(u32::MAX);
+                if places > Decimal::MAX_SCALE {
+                    return Err(PositiveError::invalid_precision(
+                        i32::try_from(places).unwrap_or(i32::MAX),
+                        "at most 28 decimal places",
+                    ));
+                }
+                Positive::new_decimal(x.to_dec().round_dp(places))
+            }
+            (Function::Clamp, [x, lo, hi]) => {
+                if lo > hi {
+                    return Err(PositiveError::invalid_value(
+                        lo.to_dec(),
+                        "clamp lower bound exceeds the upper bound",
+                    ));
+                }
+                Ok(x.clamp(*lo, *hi))
+            }
+            _ => unreachable!("arity is checked when parsing"),
+        }
+    }
+}
+
+impl Node {
+    fn eval<R>(&self, variable: &R) -> Result<Positive, ExprError>
+    where
+        R: Fn(usize) -> Result<Positive, ExprError>,
+    {
+        let result = match &self.kind {
+            NodeKind::Literal(value) => return Ok(*value),
+            NodeKind::Variable(index) => return variable(*index),
+            NodeKind::Chain(first, rest) => {
+                let mut value = first.eval(variable)?;
+                for (index, (op, operand)) in rest.iter().enumerate() {
+                    let rhs = operand.eval(variable)?;
+                    value = binary(*op, value.to_dec(), rhs.to_dec()).map_err(|error| {
+                        // Report the text up to this operand, like a
+                        // left-nested tree of binary operations would.
+                        let span = if index + 1 == rest.len() {
+                            self.span.clone()
+                        } else {
+                            first.span.start..operand.span.end
+                        };
+                        ExprError::new(span, ExprErrorKind::Positive(error))
+                    })?;
+                }
+                return Ok(value);
+            }
+            NodeKind::Call(function, args) => {
+                let values = args
+                    .iter()
+                    .map(|arg| arg.eval(variable))
+                    .collect::<Result<Vec<_>, _>>()?;
+                function.apply(&values)
+            }
+        };
+        result.map_err(|error| ExprError::new(self.span.clone(), ExprErrorKind::Positive(error)))
+    }
+}
+
+fn binary(op: BinaryOp, lhs: Decimal, rhs: Decimal) -> PositiveResult<Positive> {
+    let (value, operation) = match op {
+        BinaryOp::Add => (lhs.checked_add(rhs), Operation::Add),
+        BinaryOp::Sub => (lhs.checked_sub(rhs), Operation::Sub),
+        BinaryOp::Mul => (lhs.checked_mul(rhs), Operation::Mul),
+        BinaryOp::Div => {
+            if rhs.is_zero() {
+                return Err(PositiveError::arithmetic_error(
+                    Operation::Div,
+                    "division by zero",
+                ));
+            }
+            (lhs.checked_div(rhs).map(round_div), Operation::Div)
+        }
+    };
+    let value = value.ok_or_else(|| PositiveError::arithmetic_error(operation, "overflow"))?;
+    Positive::new_decimal(value)
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum TokenKind {
+    Number,
+    Identifier,
+    Plus,
+    Minus,
+    Star,
+    Slash,
+    Comma,
+    LeftParen,
+    RightParen,
+    End,
+}
+
+#[derive(Debug)]
+struct Token<'a> {
+    kind: TokenKind,
+    text: &'a str,
+    span: Range<usize>,
+}
+
+fn tokenize(source: &str) -> Result<Vec<Token<'_>>, ExprError> {
+    let mut tokens = Vec::new();
+    let mut chars = source.char_indices().peekable();
+    while let Some((start, c)) = chars.next() {
+        let kind = match c {
+            c if c.is_whitespace() => continue,
+            '+' => TokenKind::Plus,
+            '-' => TokenKind::Minus,
+            '*' => TokenKind::Star,
+            '/' => TokenKind::Slash,
+            ',' => TokenKind::Comma,
+            '(' => TokenKind::LeftParen,
+            ')' => TokenKind::RightParen,
+            c if c.is_ascii_digit() || c == '.' => {
+                while chars
+                    .next_if(|&(_, c)| c.is_ascii_alphanumeric() || c == '.')
+                    .is_some()
+                {}
+                TokenKind::Number
+            }
+            c if c.is_ascii_alphabetic() || c == '_' => {
+                while chars
+                    .next_if(|&(_, c)| c.is_ascii_alphanumeric() || c == '_')
+                    .is_some()
+                {}
+                TokenKind::Identifier
+            }
+            c => {
+                return Err(ExprError::syntax(
+                    start..start + c.len_utf8(),
+                    format!("unexpected character `{c}`"),
+                ));
+            }
+        };
+        let end = chars.peek().map_or(source.len(), |&(end, _)| end);
+        tokens.push(Token {
+            kind,
+            text: &source[start..end],
+            span: start..end,
+        });
+    }
+    tokens.push(Token {
+        kind: TokenKind::End,
+        text: "",
+        span: source.len()..source.len(),
+    });
+    Ok(tokens)
+}
+
+/// A recursive-descent parser over the tokens of a formula.
+struct Parser<'a> {
+    tokens: Vec<Token<'a>>,
+    position: usize,
+    /// Nesting of the node being parsed, bounded by [`MAX_DEPTH`].
+    depth: usize,
+    variables: Vec<(String, Range<usize>)>,
+}
+
+impl<'a> Parser<'a> {
+    /// `expression := term (("+" | "-") term)*`
+    fn expression(&mut self) -> Result<Node, ExprError> {
+        let first = self.term()?;
+        let mut rest = Vec::new();
+        loop {
+            let op = match self.peek().kind {
+                TokenKind::Plus => BinaryOp::Add,
+                TokenKind::Minus => BinaryOp::Sub,
+                _ => return Ok(chain_node(first, rest)),
+            };
+            self.position += 1;
+            rest.push((op, self.term()?));
+        }
+    }
+
+    /// `term := factor (("*" | "/") factor)*`
+    fn term(&mut self) -> Result<Node, ExprError> {
+        let first = self.factor()?;
+        let mut rest = Vec::new();
+        loop {
+            let op = match self.peek().kind {
+                TokenKind::Star => BinaryOp::Mul,
+                TokenKind::Slash => BinaryOp::Div,
+                _ => return Ok(chain_node(first, rest)),
+            };
+            self.position += 1;
+            rest.push((op, self.factor()?));
+        }
+    }
+
+    /// `factor := number | variable | call | "(" expression ")"`
+    fn factor(&mut self) -> Result<Node, ExprError> {
+        let token = self.peek();
+        let (kind, text, span) = (token.kind, token.text, token.span.clone());
+        match kind {
+            TokenKind::Number => {
+                self.position += 1;
+                let value = Positive::from_str(text).map_err(|error| {
+                    ExprError::new(span.clone(), ExprErrorKind::Positive(error))
+                })?;
+                Ok(Node {
+                    kind: NodeKind::Literal(value),
+                    span,
+                })
+            }
+            TokenKind::Identifier => {
+                self.position += 1;
+                if self.peek().kind == TokenKind::LeftParen {
+                    self.call(text, span)
+                } else {
+                    Ok(Node {
+                        kind: NodeKind::Variable(self.variable(text, &span)),
+                        span,
+                    })
+                }
+            }
+            TokenKind::LeftParen => {
+                self.descend()?;
+                self.position += 1;
+                let inner = self.expression()?;
+                let end = self.expect(TokenKind::RightParen, "expected `)`")?;
+                self.depth -= 1;
+                Ok(Node {
+                    kind: inner.kind,
+                    span: span.start..end,
+                })
+            }
+            TokenKind::End => Err(ExprError::syntax(span, "unexpected end of the formula")),
+            _ => Err(ExprError::syntax(
+                span,
+                format!("expected a number, a variable or `(`, found `{text}`"),
+            )),
+        }
+    }
+
+    /// `call := identifier "(" expression ("," expression)* ")"`
+    fn call(&mut self, name: &str, name_span: Range<usize>) -> Result<Node, ExprError> {
+        let function = Function::from_name(name).ok_or_else(|| {
+            ExprError::new(
+                name_span.clone(),
+                ExprErrorKind::UnknownFunction(name.to_string()),
+            )
+        })?;
+        self.descend()?;
+        self.position += 1;
+        let mut args = vec![self.expression()?];
+        while self.peek().kind == TokenKind::Comma {
+            self.position += 1;
+            args.push(self.expression()?);
+        }
+        let end = self.expect(TokenKind::RightParen, "expected `,` or `)`")?;
+        self.depth -= 1;
+        let span = name_span.start..end;
+        let (arity, expected) = function.arity();
+        if !arity.contains(&args.len()) {
+            return Err(ExprError::new(
+                span,
+                ExprErrorKind::ArgumentCount {
+                    function: function.name(),
+                    expected,
+                    found: args.len(),
+                },
+            ));
+        }
+        Ok(Node {
+            kind: NodeKind::Call(function, args),
+            span,
+        })
+    }
+
+    /// Returns the index of variable `name`, registering it on first use.
+    fn variable(&mut self, name: &str, span: &Range<usize>) -> usize {
+        match self.variables.iter().position(|(known, _)| known == name) {
+            Some(index) => index,
+            None => {
+                self.variables.push((name.to_string(), span.clone()));
+                self.variables.len() - 1
+            }
+        }
+    }
+
+    /// Enters one more level of nesting at the current token, failing
+    /// beyond [`MAX_DEPTH`].
+    fn descend(&mut self) -> Result<(), ExprError> {
+        if self.depth == MAX_DEPTH {
+            return Err(ExprError::syntax(
+                self.peek().span.clone(),
+                format!("formula nests more than {MAX_DEPTH} levels deep"),
+            ));
+        }
+        self.depth += 1;
+        Ok(())
+    }
+
+    /// Consumes a token of `kind` and returns its end offset.
+    fn expect(&mut self, kind: TokenKind, message: &str) -> Result<usize, ExprError> {
+        let token = self.peek();
+        if token.kind != kind {
+            return Err(ExprError::syntax(token.span.clone(), message));
+        }
+        let end = token.span.end;
+        self.position += 1;
+        Ok(end)
+    }
+
+    fn peek(&self) -> &Token<'a> {
+        &self.tokens[self.position]
+    }
+}
+
+fn chain_node(first: Node, rest: Vec<(BinaryOp, Node)>) -> Node {
+    let Some((_, last)) = rest.last() else {
+        return first;
+    };
+    let span = first.span.start..last.span.end;
+    Node {
+        kind: NodeKind::Chain(Box::new(first), rest),
+        span,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pos_or_panic;
+    use rust_decimal_macros::dec;
+
+    fn eval(source: &str) -> Result<Positive, ExprError> {
+        Formula::parse(source)?.eval(&[("x", pos_or_panic!(4.0)), ("y", pos_or_panic!(2.5))])
+    }
+
+    fn value(source: &str) -> Decimal {
+        eval(source).unwrap().to_dec()
+    }
+
+    #[test]
+    fn test_operators_and_precedence() {
+        assert_eq!(value("1 + 2 * 3"), dec!(7));
+        assert_eq!(value("(1 + 2) * 3"), dec!(9));
+        assert_eq!(value("10 - 4 - 3"), dec!(3));
+        assert_eq!(value("x * y / 2"), dec!(5));
+        assert_eq!(value("1 / 3"), dec!(0.3333333333333333333333333333));
+        assert_eq!(value("2 / 3"), dec!(0.6666666666666666666666666667));
+    }
+
+    #[test]
+    fn test_functions() {
+        assert_eq!(value("min(x, y, 3)"), dec!(2.5));
+        assert_eq!(value("max(x * 0.0004, 1.5)"), dec!(1.5));
+        assert_eq!(value("sqrt(x)"), dec!(2));
+        assert_eq!(value("round_to(ln(x), 4)"), dec!(1.3863));
+        assert_eq!(value("round_to(2 / 3, 4)"), dec!(0.6667));
+        assert_eq!(value("round_to(1.23456, 2.0)"), dec!(1.23));
+        assert_eq!(value("round_to(1.23456, 1.5 * 2)"), dec!(1.235));
+        assert_eq!(value("clamp(x, 1, 3)"), dec!(3));
+        assert_eq!(value("clamp(y, 3, 5)"), dec!(3));
+    }
+
+    #[test]
+    fn test_variables_in_order_of_first_use() {
+        let formula = Formula::parse("b * a + b").unwrap();
+        assert_eq!(formula.variables().collect::<Vec<_>>(), ["b", "a"]);
+        let value = formula
+            .eval_values(&[pos_or_panic!(2.0), pos_or_panic!(3.0)])
+            .unwrap();
+        assert_eq!(value.to_dec(), dec!(8));
+
+        let map: BTreeMap<&str, Positive> =
+            [("a", pos_or_panic!(3.0)), ("b", pos_or_panic!(2.0))].into();
+        assert_eq!(formula.eval(&map).unwrap(), value);
+        let lookup = |name: &str| (name != "c").then_some(pos_or_panic!(1.0));
+        assert_eq!(formula.eval(&lookup).unwrap().to_dec(), dec!(2));
+
+        let err = formula.eval_values(&[pos_or_panic!(2.0)]).unwrap_err();
+        assert_eq!(
+            err.kind,
+            ExprErrorKind::ValueCount {
+                expected: 2,
+                found: 1
+            }
+        );
+        assert_eq!(err.span, 0..9);
+    }
+
+    #[test]
+    fn test_parse_errors_have_spans() {
+        for (source, span) in [
+            ("", 0..0),
+            ("1 +", 3..3),
+            ("(1 + 2", 6..6),
+            ("2 3", 2..3),
+            ("-1", 0..1),
+            ("1 # 2", 2..3),
+            ("pow(2, 3)", 0..3),
+            ("clamp(1, 2)", 0..11),
+            ("min(1)", 0..6),
+            ("1.2.3 + x", 0..5),
+        ] {
+            let err = Formula::parse(source).unwrap_err();
+            assert_eq!(err.span, span, "{source}: {err}");
+        }
+        let err = Formula::parse("sqrt(1, 2)").unwrap_err();
+        assert_eq!(err.to_string(), "`sqrt` takes 1 argument, found 2 at 0..10");
+    }
+
+    #[test]
+    fn test_nesting_is_limited() {
+        let nested = |depth: usize| format!("{}1{}", "(".repeat(depth), ")".repeat(depth));
+        assert!(Formula::parse(&nested(MAX_DEPTH)).is_ok());
+        let err = Formula::parse(&nested(MAX_DEPTH + 1)).unwrap_err();
+        assert_eq!(err.span, MAX_DEPTH..MAX_DEPTH + 1);
+        assert!(matches!(err.kind, ExprErrorKind::Syntax(_)));
+        assert!(Formula::parse(&nested(30_000)).is_err());
+
+        let calls = |depth: usize| format!("{}4{}", "sqrt(".repeat(depth), ")".repeat(depth));
+        let deepest = Formula::parse(&calls(MAX_DEPTH)).unwrap();
+        assert_eq!(deepest.eval(&|_: &str| None).unwrap(), Positive::ONE);
+        assert!(Formula::parse(&calls(MAX_DEPTH + 1)).is_err());
+    }
+
+    #[test]
+    fn test_operator_chains_are_not_nesting() {
+        let sum = Formula::parse(&vec!["1"; 130].join(" + ")).unwrap();
+        assert_eq!(sum.eval(&|_: &str| None).unwrap(), pos_or_panic!(130.0));
+
+        let long = Formula::parse(&vec!["x"; 30_000].join("+")).unwrap();
+        assert_eq!(
+            long.eval_values(&[Positive::ONE]).unwrap(),
+            pos_or_panic!(30_000.0)
+        );
+
+        let product = Formula::parse(&vec!["2"; 30_000].join("*")).unwrap();
+        let err = product.eval(&|_: &str| None).unwrap_err();
+        assert_eq!(err.kind.error().unwrap().code(), "arithmetic");
+        // 2^96 is the first power of two beyond the `Decimal` range.
+        assert_eq!(err.span, 0.."2*".repeat(95).len() + 1);
+
+        // A chain inside the deepest parentheses fails at its second step.
+        let chain = format!("1.5 - {}", vec!["1"; 200].join(" - "));
+        let nested = format!("{}{chain}{}", "(".repeat(MAX_DEPTH), ")".repeat(MAX_DEPTH));
+        let err = Formula::parse(&nested)
+            .unwrap()
+            .eval(&|_: &str| None)
+            .unwrap_err();
+        assert_eq!(err.span, MAX_DEPTH..MAX_DEPTH + "1.5 - 1 - 1".len());
+    }
+
+    #[test]
+    fn test_eval_errors_have_spans() {
+        let err = eval("1 + (x - 5) * 2").unwrap_err();
+        assert_eq!(err.span, 4..11);
+        assert_eq!(err.kind.error().unwrap().code(), "out_of_bounds");
+
+        let err = eval("x / (y - 2.5)").unwrap_err();
+        if cfg!(feature = "non-zero") {
+            assert_eq!(err.span, 4..13);
+            assert_eq!(err.kind.error().unwrap().code(), "out_of_bounds");
+        } else {
+            assert_eq!(err.span, 0..13);
+            assert_eq!(err.kind.error().unwrap().code(), "arithmetic");
+        }
+
+        let err = eval("x + z").unwrap_err();
+        assert_eq!(err.span, 4..5);
+        assert_eq!(err.kind, ExprErrorKind::UnknownVariable("z".into()));
+
+        assert_eq!(eval("ln(0.5)").unwrap_err().span, 0..7);
+        assert_eq!(
+            eval("round_to(x, 1.5)")
+                .unwrap_err()
+                .kind
+                .error()
+                .unwrap()
+                .code(),
+            "invalid_value"
+        );
+        assert_eq!(
+            eval("round_to(x, 29)")
+                .unwrap_err()
+                .kind
+                .error()
+                .unwrap()
+                .code(),
+            "invalid_precision"
+        );
+        assert_eq!(
+            eval("clamp(x, 3, 2)")
+                .unwrap_err()
+                .kind
+                .error()
+                .unwrap()
+                .code(),
+            "invalid_value"
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_round_trip() {
+        let formula: Formula = serde_json::from_str("\"max(notional * 0.0004, 1.5)\"").unwrap();
+        assert_eq!(
+            serde_json::to_string(&formula).unwrap(),
+            "\"max(notional * 0.0004, 1.5)\""
+        );
+        assert!(serde_json::from_str::<Formula>("\"max(1,\"").is_err());
+    }
+}
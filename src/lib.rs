@@ -28,7 +28,37 @@
 //! - **Serde Support**: Full serialization/deserialization support for JSON and other formats
 //! - **Approx Support**: Approximate equality comparisons for floating-point tolerance
 //! - **Checked Operations**: Safe arithmetic operations that return `Result` instead of panicking
+//! - **Formulas**: The `expr` module compiles formulas such as `max(notional * 0.0004, 1.5)`
+//!   once and evaluates them over named variables with checked arithmetic and located errors
 //! - **Optional utoipa Integration**: OpenAPI schema generation support via feature flag
+//! - **Optional schemars Integration**: JSON Schema generation support via feature flag
+//! - **Compact Binary Encodings**: Exact `(mantissa, scale)` encoding for binary serde
+//!   formats such as bincode and postcard, and optional `borsh` support
+//! - **Zero-Copy Archives**: Optional `rkyv` support with a validated, in-place readable
+//!   `ArchivedPositive`
+//! - **Ordered Keys**: `to_ordered_bytes` / `to_ordered_bytes_var` encodings whose byte
+//!   order matches numeric order, for ordered key-value stores
+//! - **Postgres via sqlx**: Optional `sqlx-postgres` feature mapping `Positive` to
+//!   `NUMERIC`, validating the invariant on decode
+//! - **Diesel Integration**: Optional `diesel` feature for Postgres `Numeric` columns,
+//!   plus `diesel-sqlite` for SQLite `Double`/`Text` columns
+//! - **SQLite via rusqlite**: Optional `rusqlite` feature storing `Positive` as exact
+//!   `TEXT`, with `REAL`/`INTEGER` wrappers, reading back any of those column types
+//! - **MongoDB Decimal128**: Optional `bson` feature converting `Positive` to and from
+//!   BSON `Decimal128`, with a serde adapter that writes `Decimal128` fields
+//! - **Protobuf via prost**: Optional `prost` feature with an exact `positive.v1.Positive`
+//!   message (`proto/positive/v1/positive.proto`) and conversions to and from it
+//! - **Apache Arrow Columns**: Optional `arrow` feature with `PositiveArray`, a validated
+//!   `Decimal128Array` with exact import/export, `sum`/`min`/`max` and quantization
+//! - **Polars Series**: Optional `polars` feature converting `Positive` values to and
+//!   from `Decimal` series and reporting the invalid rows of a `DataFrame` column
+//! - **CSV Validation**: Optional `csv` feature with a streaming reader that parses named
+//!   columns, collects errors with line numbers and writes normalized output
+//! - **Invariant Checking**: Optional `debug-invariants` feature that re-asserts the
+//!   positivity invariant on every operation and panics naming the offending operation
+//! - **Violation Policy**: Choose whether operator overflows and invariant violations
+//!   panic, saturate, or invoke a callback, process-wide or per thread
+//! - **`no_std` Support**: Builds on `core` + `alloc` with the default `std` feature disabled
 //!
 //! ## Installation
 //!
@@ -53,6 +83,100 @@
 //! positive = { version = "0.4", features = ["utoipa"] }
 //! ```
 //!
+//! To enable JSON Schema support via `schemars`:
+//!
+//! ```toml
+//! [dependencies]
+//! positive = { version = "0.4", features = ["schemars"] }
+//! ```
+//!
+//! To enable Borsh serialization:
+//!
+//! ```toml
+//! [dependencies]
+//! positive = { version = "0.4", features = ["borsh"] }
+//! ```
+//!
+//! To archive values with `rkyv` for zero-copy access:
+//!
+//! ```toml
+//! [dependencies]
+//! positive = { version = "0.4", features = ["rkyv"] }
+//! ```
+//!
+//! To read and write Postgres `NUMERIC` columns with `sqlx`:
+//!
+//! ```toml
+//! [dependencies]
+//! positive = { version = "0.4", features = ["sqlx-postgres"] }
+//! ```
+//!
+//! To use `Positive` in Diesel schema structs with Postgres, enable `diesel`;
+//! add `diesel-sqlite` for SQLite:
+//!
+//! ```toml
+//! [dependencies]
+//! positive = { version = "0.4", features = ["diesel"] }
+//! ```
+//!
+//! To bind and read `Positive` with `rusqlite`:
+//!
+//! ```toml
+//! [dependencies]
+//! positive = { version = "0.4", features = ["rusqlite"] }
+//! ```
+//!
+//! To store `Positive` as MongoDB `Decimal128`:
+//!
+//! ```toml
+//! [dependencies]
+//! positive = { version = "0.4", features = ["bson"] }
+//! ```
+//!
+//! To exchange `Positive` in protobuf messages:
+//!
+//! ```toml
+//! [dependencies]
+//! positive = { version = "0.4", features = ["prost"] }
+//! ```
+//!
+//! To move `Positive` columns in and out of Arrow:
+//!
+//! ```toml
+//! [dependencies]
+//! positive = { version = "0.4", features = ["arrow"] }
+//! ```
+//!
+//! To convert `Positive` columns to and from polars:
+//!
+//! ```toml
+//! [dependencies]
+//! positive = { version = "0.4", features = ["polars"] }
+//! ```
+//!
+//! To validate and normalize CSV files:
+//!
+//! ```toml
+//! [dependencies]
+//! positive = { version = "0.4", features = ["csv"] }
+//! ```
+//!
+//! ### `no_std`
+//!
+//! The default features are `std`, `serde` and `approx`. Disable them to build
+//! on `core` + `alloc`, re-enabling the integrations you need:
+//!
+//! ```toml
+//! [dependencies]
+//! positive = { version = "0.4", default-features = false, features = ["serde"] }
+//! ```
+//!
+//! Without `std`, arithmetic operators always panic on a violation (the
+//! [`policy`] module only exposes its types), and
+//! `PositiveError::ParseError` does not expose the `rust_decimal` error as
+//! its `source`. `PositiveError` implements `core::error::Error` in every
+//! configuration. The `utoipa` and `schemars` features require `std`.
+//!
 //! ## Quick Start
 //!
 //! ```rust
@@ -222,6 +346,31 @@
 //! - `ConversionError` - Error when converting between types
 //! - `OutOfBounds` - Value exceeds defined limits
 //! - `InvalidPrecision` - Invalid decimal precision settings
+//! - `ParseError` - Text could not be parsed as a decimal
+//! - `ExpressionError` - A step of a `PositiveExpr` failed
+//!
+//! `PositiveError` is `#[non_exhaustive]`. Values are reported as exact `Decimal`s,
+//! arithmetic failures name the failing [`Operation`], and
+//! [`PositiveError::code`] returns a stable machine-readable code:
+//!
+//! ```rust
+//! use positive::{Decimal, Positive, PositiveError};
+//!
+//! let err = Positive::new_decimal(Decimal::new(-1, 19)).unwrap_err();
+//! assert_eq!(err.code(), "out_of_bounds");
+//! assert!(matches!(err, PositiveError::OutOfBounds { value, .. } if value == Decimal::new(-1, 19)));
+//! ```
+//!
+//! Arithmetic operators cannot return errors. By default they panic on overflow
+//! or when the result would not be positive; the [`policy`] module lets you
+//! saturate or invoke a callback instead:
+//!
+//! ```rust
+//! use positive::{Positive, ViolationPolicy, scoped_policy};
+//!
+//! let _guard = scoped_policy(ViolationPolicy::Saturate);
+//! assert_eq!(Positive::INFINITY + Positive::ONE, Positive::INFINITY);
+//! ```
 //!
 //! ## Serialization
 //!
@@ -235,6 +384,11 @@
 //! let parsed: positive::Positive = serde_json::from_str(&json).unwrap();
 //! ```
 //!
+//! Binary formats (whose serializer is not human-readable, e.g. bincode and
+//! postcard) use an exact `(mantissa, scale)` pair instead of an `f64`, and the
+//! `borsh` feature adds `BorshSerialize`/`BorshDeserialize` using the 16-byte
+//! `Decimal` layout. Both validate the value when decoding.
+//!
 //! ## Use Cases
 //!
 //! - **Financial Applications**: Prices, quantities, fees, rates
@@ -247,15 +401,60 @@
 //! This project is licensed under the MIT License.
 //!
 
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+#[cfg(feature = "approx")]
+mod approx_eq;
+#[cfg(feature = "rkyv")]
+mod archive;
+#[cfg(feature = "arrow")]
+mod arrow;
+mod binary;
+mod checked;
 pub mod constants;
+#[cfg(feature = "csv")]
+pub mod csv;
+#[cfg(feature = "polars")]
+pub mod dataframe;
+#[cfg(feature = "serde")]
+pub mod de;
+#[cfg(feature = "bson")]
+pub mod decimal128;
+#[cfg(feature = "diesel")]
+mod diesel_sql;
 pub mod error;
+pub mod expr;
 #[macro_use]
 pub mod macros;
+mod ordered;
+pub mod policy;
 mod positive;
 pub mod prelude;
+#[cfg(feature = "prost")]
+pub mod proto;
+mod range;
+#[cfg(feature = "rusqlite")]
+pub mod sqlite;
+#[cfg(feature = "sqlx-postgres")]
+mod sqlx_postgres;
 mod tests;
-pub use error::{PositiveError, PositiveResult};
+mod tolerance;
+#[cfg(feature = "approx")]
+pub use approx_eq::Approx;
+#[cfg(feature = "rkyv")]
+pub use archive::ArchivedPositive;
+#[cfg(feature = "arrow")]
+pub use arrow::PositiveArray;
+pub use checked::PositiveExpr;
+pub use error::{Operation, PositiveError, PositiveResult};
+pub use policy::ViolationPolicy;
+#[cfg(feature = "std")]
+pub use policy::{scoped_policy, set_global_policy};
 pub use positive::{DIV_ROUNDING_STRATEGY, Positive, is_positive, is_valid_positive_value};
+pub use range::PositiveRange;
+pub use tolerance::{Tolerance, ToleranceOrd};
 
 /// Re-export rust_decimal for convenience.
 pub use rust_decimal::Decimal;
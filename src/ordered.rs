@@ -0,0 +1,301 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 18/10/26
+******************************************************************************/
+
+//! Order-preserving byte encodings of `Positive`.
+//!
+//! Ordered key-value stores (sled, RocksDB, LMDB, ...) sort keys
+//! lexicographically. The encodings below map `Positive` values to bytes
+//! whose lexicographic order matches the numeric order, whatever the
+//! `Decimal` scale of the inputs, so `Positive` prices can be used directly
+//! as keys and range-scanned.
+//!
+//! Values are normalized: `1.5` and `1.50` encode to the same key and decode
+//! to the value with trailing zeros removed. `Positive::INFINITY` produces
+//! the largest key of either encoding.
+//!
+//! - [`Positive::to_ordered_bytes`] is fixed-width
+//!   ([`Positive::ORDERED_BYTES_LEN`] bytes): the integer part as a
+//!   12-byte big-endian integer followed by the fractional part scaled to
+//!   28 digits, also as a 12-byte big-endian integer.
+//! - [`Positive::to_ordered_bytes_var`] is variable-width (1 to 25 bytes):
+//!   one byte with the length of the integer part, its minimal big-endian
+//!   bytes, then the 12-byte fractional part with trailing zero bytes
+//!   removed.
+//!
+//! ```rust
+//! use positive::{Positive, pos_or_panic};
+//!
+//! let low = pos_or_panic!(9.75);
+//! let high = pos_or_panic!(10.5);
+//! assert!(low.to_ordered_bytes() < high.to_ordered_bytes());
+//! assert!(low.to_ordered_bytes_var() < high.to_ordered_bytes_var());
+//!
+//! let key = high.to_ordered_bytes_var();
+//! assert_eq!(Positive::from_ordered_bytes_var(&key).unwrap(), high);
+//! ```
+
+use crate::binary::{MAX_MANTISSA, from_parts};
+use crate::error::{PositiveError, PositiveResult};
+use crate::positive::Positive;
+use alloc::format;
+use alloc::vec::Vec;
+use rust_decimal::Decimal;
+
+/// Width in bytes of each half of the encoding.
+const PART_LEN: usize = 12;
+
+/// The fractional part is stored as a multiple of `10^-28`.
+const FRACTION_SCALE: u32 = Decimal::MAX_SCALE;
+
+const fn pow10(exp: u32) -> u128 {
+    10u128.pow(exp)
+}
+
+impl Positive {
+    /// Length in bytes of [`Positive::to_ordered_bytes`].
+    pub const ORDERED_BYTES_LEN: usize = 2 * PART_LEN;
+
+    /// Encodes the value as fixed-width bytes whose lexicographic order
+    /// matches the numeric order.
+    ///
+    /// Numerically equal values produce the same bytes regardless of their
+    /// scale.
+    #[must_use]
+    pub fn to_ordered_bytes(&self) -> [u8; Self::ORDERED_BYTES_LEN] {
+        let (integer, fraction) = self.split();
+        let mut bytes = [0u8; Self::ORDERED_BYTES_LEN];
+        bytes[..PART_LEN].copy_from_slice(&part_bytes(integer));
+        bytes[PART_LEN..].copy_from_slice(&part_bytes(fraction));
+        bytes
+    }
+
+    /// Decodes bytes produced by [`Positive::to_ordered_bytes`].
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ConversionError` if `bytes` is not
+    /// [`Positive::ORDERED_BYTES_LEN`] long or does not encode a
+    /// representable `Decimal`, and the usual validation errors (e.g. zero
+    /// under the `non-zero` feature).
+    pub fn from_ordered_bytes(bytes: &[u8]) -> PositiveResult<Positive> {
+        if bytes.len() != Self::ORDERED_BYTES_LEN {
+            return Err(malformed(&format!(
+                "expected {} bytes, got {}",
+                Self::ORDERED_BYTES_LEN,
+                bytes.len()
+            )));
+        }
+        let (integer, fraction) = bytes.split_at(PART_LEN);
+        join(read_part(integer), read_part(fraction))
+    }
+
+    /// Encodes the value as variable-width bytes (1 to 25 bytes) whose
+    /// lexicographic order matches the numeric order.
+    ///
+    /// Numerically equal values produce the same bytes regardless of their
+    /// scale. Small integers and short fractions produce short keys.
+    #[must_use]
+    pub fn to_ordered_bytes_var(&self) -> Vec<u8> {
+        let (integer, fraction) = self.split();
+        let integer = part_bytes(integer);
+        let integer_start = integer.iter().take_while(|&&b| b == 0).count();
+        let fraction = part_bytes(fraction);
+        let fraction_end = PART_LEN - fraction.iter().rev().take_while(|&&b| b == 0).count();
+
+        let mut bytes = Vec::with_capacity(1 + PART_LEN - integer_start + fraction_end);
+        // At most `PART_LEN` (12), so the length fits in one byte.
+        bytes.push((PART_LEN - integer_start) as u8);
+        bytes.extend_from_slice(&integer[integer_start..]);
+        bytes.extend_from_slice(&fraction[..fraction_end]);
+        bytes
+    }
+
+    /// Decodes bytes produced by [`Positive::to_ordered_bytes_var`].
+    ///
+    /// Only the canonical encoding is accepted: the integer part must have
+    /// no leading zero byte and the fractional part no trailing zero byte.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ConversionError` if `bytes` is not a canonical encoding of
+    /// a representable `Decimal`, and the usual validation errors (e.g. zero
+    /// under the `non-zero` feature).
+    pub fn from_ordered_bytes_var(bytes: &[u8]) -> PositiveResult<Positive> {
+        let (&integer_len, rest) = bytes
+            .split_first()
+            .ok_or_else(|| malformed("empty input"))?;
+        let integer_len = usize::from(integer_len);
+        if integer_len > PART_LEN || rest.len() < integer_len {
+            return Err(malformed("invalid integer length"));
+        }
+        let (integer, fraction) = rest.split_at(integer_len);
+        if integer.first() == Some(&0) {
+            return Err(malformed("non-canonical integer part"));
+        }
+        if fraction.len() > PART_LEN {
+            return Err(malformed("fractional part too long"));
+        }
+        if fraction.last() == Some(&0) {
+            return Err(malformed("non-canonical fractional part"));
+        }
+
+        let mut fraction_part = [0u8; PART_LEN];
+        fraction_part[..fraction.len()].copy_from_slice(fraction);
+        join(read_part(integer), read_part(&fraction_part))
+    }
+
+    /// Splits the value into its integer part and its fractional part
+    /// scaled to `10^28`.
+    fn split(&self) -> (u128, u128) {
+        let value = self.to_dec();
+        let mantissa = value.mantissa().unsigned_abs();
+        let unit = pow10(value.scale());
+        (
+            mantissa / unit,
+            (mantissa % unit) * pow10(FRACTION_SCALE - value.scale()),
+        )
+    }
+}
+
+/// Big-endian bytes of a part, which always fits in 96 bits.
+fn part_bytes(part: u128) -> [u8; PART_LEN] {
+    let mut bytes = [0u8; PART_LEN];
+    bytes.copy_from_slice(&part.to_be_bytes()[16 - PART_LEN..]);
+    bytes
+}
+
+fn read_part(bytes: &[u8]) -> u128 {
+    bytes
+        .iter()
+        .fold(0u128, |acc, &byte| (acc << 8) | u128::from(byte))
+}
+
+/// Rebuilds the value from its integer and fractional parts, using the
+/// smallest scale that represents it exactly.
+fn join(integer: u128, fraction: u128) -> PositiveResult<Positive> {
+    if fraction >= pow10(FRACTION_SCALE) {
+        return Err(malformed("fractional part out of range"));
+    }
+    let mut fraction = fraction;
+    let mut scale = FRACTION_SCALE;
+    while scale > 0 && fraction.is_multiple_of(10) {
+        fraction /= 10;
+        scale -= 1;
+    }
+    let mantissa = integer
+        .checked_mul(pow10(scale))
+        .and_then(|scaled| scaled.checked_add(fraction))
+        .filter(|&mantissa| mantissa <= MAX_MANTISSA)
+        .ok_or_else(|| malformed("value not representable as Decimal"))?;
+    from_parts(mantissa, false, scale)
+}
+
+#[cold]
+fn malformed(reason: &str) -> PositiveError {
+    PositiveError::conversion_error("ordered bytes", "Positive", reason)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    use rust_decimal_macros::dec;
+
+    /// Arbitrary valid `Positive` with an arbitrary scale.
+    fn positive() -> impl Strategy<Value = Positive> {
+        (0..=MAX_MANTISSA, 0..=Decimal::MAX_SCALE).prop_filter_map("invalid", |(m, s)| {
+            Positive::new_decimal(Decimal::from_i128_with_scale(m as i128, s)).ok()
+        })
+    }
+
+    /// Values concentrated around a few magnitudes so that ties and close
+    /// neighbours with different scales are common.
+    fn clustered() -> impl Strategy<Value = Positive> {
+        (0u128..2_000, 0u32..6, 0u32..6).prop_filter_map("invalid", |(m, s, pad)| {
+            let mantissa = m * pow10(pad);
+            Positive::new_decimal(Decimal::from_i128_with_scale(mantissa as i128, s + pad)).ok()
+        })
+    }
+
+    fn any_positive() -> impl Strategy<Value = Positive> {
+        prop_oneof![positive(), clustered()]
+    }
+
+    proptest! {
+        #[test]
+        fn prop_fixed_preserves_order(a in any_positive(), b in any_positive()) {
+            prop_assert_eq!(a.cmp(&b), a.to_ordered_bytes().cmp(&b.to_ordered_bytes()));
+        }
+
+        #[test]
+        fn prop_var_preserves_order(a in any_positive(), b in any_positive()) {
+            prop_assert_eq!(a.cmp(&b), a.to_ordered_bytes_var().cmp(&b.to_ordered_bytes_var()));
+        }
+
+        #[test]
+        fn prop_round_trip(a in any_positive()) {
+            let fixed = Positive::from_ordered_bytes(&a.to_ordered_bytes()).unwrap();
+            let var = Positive::from_ordered_bytes_var(&a.to_ordered_bytes_var()).unwrap();
+            prop_assert_eq!(fixed.to_dec(), a.to_dec().normalize());
+            prop_assert_eq!(var.to_dec(), a.to_dec().normalize());
+        }
+
+        #[test]
+        fn prop_infinity_is_maximal(a in any_positive()) {
+            prop_assert!(a.to_ordered_bytes() <= Positive::INFINITY.to_ordered_bytes());
+            prop_assert!(a.to_ordered_bytes_var() <= Positive::INFINITY.to_ordered_bytes_var());
+        }
+    }
+
+    #[test]
+    fn test_equal_values_share_a_key() {
+        let a = Positive::new_decimal(dec!(1.5)).unwrap();
+        let b = Positive::new_decimal(dec!(1.5000)).unwrap();
+        assert_eq!(a.to_ordered_bytes(), b.to_ordered_bytes());
+        assert_eq!(a.to_ordered_bytes_var(), b.to_ordered_bytes_var());
+        assert_eq!(
+            a.to_ordered_bytes_var(),
+            [1, 1, 0x10, 0x27, 0xE7, 0x2F, 0x1F, 0x12, 0x81, 0x30, 0x88]
+        );
+    }
+
+    #[test]
+    fn test_infinity_key() {
+        let fixed = Positive::INFINITY.to_ordered_bytes();
+        assert_eq!(&fixed[..PART_LEN], &[0xFF; PART_LEN]);
+        assert_eq!(&fixed[PART_LEN..], &[0; PART_LEN]);
+        assert_eq!(
+            Positive::INFINITY.to_ordered_bytes_var().len(),
+            1 + PART_LEN
+        );
+    }
+
+    #[test]
+    fn test_rejects_malformed_input() {
+        assert!(Positive::from_ordered_bytes(&[0; 3]).is_err());
+        assert!(Positive::from_ordered_bytes(&[0xFF; Positive::ORDERED_BYTES_LEN]).is_err());
+        assert!(Positive::from_ordered_bytes_var(&[]).is_err());
+        assert!(Positive::from_ordered_bytes_var(&[13]).is_err());
+        assert!(Positive::from_ordered_bytes_var(&[2, 1]).is_err());
+        assert!(Positive::from_ordered_bytes_var(&[1, 0]).is_err());
+        assert!(Positive::from_ordered_bytes_var(&[1, 1, 5, 0]).is_err());
+    }
+
+    #[cfg(feature = "non-zero")]
+    #[test]
+    fn test_zero_key_rejected_under_non_zero() {
+        assert!(Positive::from_ordered_bytes_var(&[0]).is_err());
+    }
+
+    #[cfg(not(feature = "non-zero"))]
+    #[test]
+    fn test_zero_is_minimal() {
+        let zero = Positive::new_decimal(Decimal::ZERO).unwrap();
+        assert_eq!(zero.to_ordered_bytes(), [0; Positive::ORDERED_BYTES_LEN]);
+        assert_eq!(zero.to_ordered_bytes_var(), [0]);
+        assert_eq!(Positive::from_ordered_bytes_var(&[0]).unwrap(), zero);
+    }
+}
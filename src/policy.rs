@@ -0,0 +1,317 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 18/10/26
+******************************************************************************/
+
+//! Configurable handling of overflow and invariant violations.
+//!
+//! The arithmetic operators on `Positive` (`+`, `-`, `*`, `/` and their
+//! assigning and mixed-type forms) cannot return a `Result`. When one of
+//! them overflows the `Decimal` range or would produce a value that breaks
+//! the positivity invariant, the violation is routed through the active
+//! [`ViolationPolicy`]:
+//!
+//! - [`ViolationPolicy::Panic`] (the default) panics, exactly as before.
+//! - [`ViolationPolicy::Saturate`] returns a saturated value instead:
+//!   `Positive::INFINITY` on overflow or division by zero, and `ZERO`
+//!   (the smallest positive `Decimal` under the `non-zero` feature) when
+//!   the result would be negative. Operators returning a `Decimal`
+//!   saturate to `Decimal::MAX` / `Decimal::MIN` by the sign of the result.
+//! - [`ViolationPolicy::Callback`] invokes a registered function with the
+//!   [`Violation`] and then saturates.
+//!
+//! The policy is process-wide ([`set_global_policy`]) and can be
+//! overridden for the current thread with [`scoped_policy`]. Every
+//! violation, whatever the policy, is counted in [`violation_counts`].
+//!
+//! The checked methods (`checked_sub`, `checked_div`, ...) are unaffected:
+//! they always return an error.
+//!
+//! Selecting a policy and counting violations need the `std` feature;
+//! without it, violations always panic.
+//!
+//! ```rust
+//! use positive::policy::{ViolationPolicy, scoped_policy};
+//! use positive::pos_or_panic;
+//!
+//! let _guard = scoped_policy(ViolationPolicy::Saturate);
+//! let diff = pos_or_panic!(1.0) - pos_or_panic!(2.0);
+//! assert!(diff.to_dec() <= positive::Decimal::ONE);
+//! ```
+
+use core::fmt;
+#[cfg(feature = "std")]
+use std::cell::Cell;
+#[cfg(feature = "std")]
+use std::marker::PhantomData;
+#[cfg(feature = "std")]
+use std::sync::atomic::{AtomicU64, Ordering};
+#[cfg(feature = "std")]
+use std::sync::{PoisonError, RwLock};
+
+/// The category of a violation reported to a [`ViolationPolicy`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ViolationKind {
+    /// The operation overflowed the `Decimal` range.
+    Overflow,
+    /// The result would break the positivity invariant (negative, zero
+    /// under the `non-zero` feature, division by zero, or an operand that
+    /// is not representable as `Decimal`).
+    Invariant,
+}
+
+/// A violation raised by a `Positive` arithmetic operator.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Violation {
+    /// The category of the violation.
+    pub kind: ViolationKind,
+    /// The name of the operator that raised it (e.g. `"sub"`, `"mul_f64"`).
+    pub operation: &'static str,
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            ViolationKind::Overflow => {
+                write!(f, "Positive arithmetic overflow in {}", self.operation)
+            }
+            ViolationKind::Invariant => write!(
+                f,
+                "Positive invariant broken in {}: result would be non-positive",
+                self.operation
+            ),
+        }
+    }
+}
+
+/// How `Positive` arithmetic operators react to a [`Violation`].
+#[derive(Clone, Copy, Debug, Default)]
+pub enum ViolationPolicy {
+    /// Panic with the violation message.
+    #[default]
+    Panic,
+    /// Return a saturated value.
+    Saturate,
+    /// Invoke the callback, then return a saturated value.
+    Callback(fn(&Violation)),
+}
+
+/// Snapshot of the number of violations observed since start-up or the
+/// last [`reset_violation_counts`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct ViolationCounts {
+    /// Number of [`ViolationKind::Overflow`] violations.
+    pub overflow: u64,
+    /// Number of [`ViolationKind::Invariant`] violations.
+    pub invariant: u64,
+}
+
+impl ViolationCounts {
+    /// Total number of violations of any kind.
+    #[must_use]
+    pub fn total(&self) -> u64 {
+        self.overflow + self.invariant
+    }
+}
+
+#[cfg(feature = "std")]
+static GLOBAL_POLICY: RwLock<ViolationPolicy> = RwLock::new(ViolationPolicy::Panic);
+#[cfg(feature = "std")]
+static OVERFLOW_COUNT: AtomicU64 = AtomicU64::new(0);
+#[cfg(feature = "std")]
+static INVARIANT_COUNT: AtomicU64 = AtomicU64::new(0);
+
+#[cfg(feature = "std")]
+thread_local! {
+    static THREAD_POLICY: Cell<Option<ViolationPolicy>> = const { Cell::new(None) };
+}
+
+/// Sets the process-wide violation policy.
+///
+/// Threads holding a [`PolicyGuard`] keep using their scoped policy.
+#[cfg(feature = "std")]
+pub fn set_global_policy(policy: ViolationPolicy) {
+    *GLOBAL_POLICY
+        .write()
+        .unwrap_or_else(PoisonError::into_inner) = policy;
+}
+
+/// Returns the process-wide violation policy.
+#[cfg(feature = "std")]
+#[must_use]
+pub fn global_policy() -> ViolationPolicy {
+    *GLOBAL_POLICY.read().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Returns the policy in effect on the current thread: the innermost
+/// scoped policy if any, otherwise the process-wide one.
+#[cfg(feature = "std")]
+#[must_use]
+pub fn current_policy() -> ViolationPolicy {
+    THREAD_POLICY.with(Cell::get).unwrap_or_else(global_policy)
+}
+
+/// Overrides the violation policy for the current thread until the
+/// returned guard is dropped.
+///
+/// Guards nest: dropping one restores the policy that was in effect when
+/// it was created.
+#[cfg(feature = "std")]
+#[must_use = "the policy is restored as soon as the guard is dropped"]
+pub fn scoped_policy(policy: ViolationPolicy) -> PolicyGuard {
+    let previous = THREAD_POLICY.with(|cell| cell.replace(Some(policy)));
+    PolicyGuard {
+        previous,
+        _not_send: PhantomData,
+    }
+}
+
+/// Restores the previous thread-local policy when dropped.
+///
+/// Returned by [`scoped_policy`]. Not `Send`, because it restores state of
+/// the thread that created it.
+#[cfg(feature = "std")]
+#[derive(Debug)]
+pub struct PolicyGuard {
+    previous: Option<ViolationPolicy>,
+    _not_send: PhantomData<*const ()>,
+}
+
+#[cfg(feature = "std")]
+impl Drop for PolicyGuard {
+    fn drop(&mut self) {
+        THREAD_POLICY.with(|cell| cell.set(self.previous));
+    }
+}
+
+/// Returns the number of violations observed so far, across all threads.
+#[cfg(feature = "std")]
+#[must_use]
+pub fn violation_counts() -> ViolationCounts {
+    ViolationCounts {
+        overflow: OVERFLOW_COUNT.load(Ordering::Relaxed),
+        invariant: INVARIANT_COUNT.load(Ordering::Relaxed),
+    }
+}
+
+/// Resets the violation counters to zero.
+#[cfg(feature = "std")]
+pub fn reset_violation_counts() {
+    OVERFLOW_COUNT.store(0, Ordering::Relaxed);
+    INVARIANT_COUNT.store(0, Ordering::Relaxed);
+}
+
+/// Counts a violation and applies the current policy to it.
+///
+/// Returns normally when the caller should saturate; panics under
+/// [`ViolationPolicy::Panic`].
+#[cfg(feature = "std")]
+#[cold]
+#[inline(never)]
+pub(crate) fn report(kind: ViolationKind, operation: &'static str) {
+    let counter = match kind {
+        ViolationKind::Overflow => &OVERFLOW_COUNT,
+        ViolationKind::Invariant => &INVARIANT_COUNT,
+    };
+    counter.fetch_add(1, Ordering::Relaxed);
+    let violation = Violation { kind, operation };
+    match current_policy() {
+        ViolationPolicy::Panic => panic!("{violation}"),
+        ViolationPolicy::Saturate => {}
+        ViolationPolicy::Callback(callback) => callback(&violation),
+    }
+}
+
+/// Panics with the violation message; without `std` there is no policy
+/// state to consult.
+#[cfg(not(feature = "std"))]
+#[cold]
+#[inline(never)]
+pub(crate) fn report(kind: ViolationKind, operation: &'static str) {
+    panic!("{}", Violation { kind, operation })
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::Positive;
+    use crate::positive::SATURATED_FLOOR;
+    use rust_decimal::Decimal;
+    use std::sync::atomic::AtomicUsize;
+
+    #[test]
+    fn test_default_policy_is_panic() {
+        assert!(matches!(current_policy(), ViolationPolicy::Panic));
+        let result = std::panic::catch_unwind(|| pos_or_panic!(1.0) - pos_or_panic!(2.0));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_saturate_sub_and_div_by_zero() {
+        let _guard = scoped_policy(ViolationPolicy::Saturate);
+        let before = violation_counts();
+        let diff = pos_or_panic!(1.0) - pos_or_panic!(2.0);
+        assert_eq!(diff.to_dec(), SATURATED_FLOOR);
+        let quotient = pos_or_panic!(1.0) / 0.0;
+        assert_eq!(quotient, Positive::INFINITY);
+        let after = violation_counts();
+        assert!(after.invariant >= before.invariant + 2);
+    }
+
+    #[test]
+    fn test_saturate_overflow() {
+        let _guard = scoped_policy(ViolationPolicy::Saturate);
+        let before = violation_counts();
+        assert_eq!(Positive::INFINITY + Positive::ONE, Positive::INFINITY);
+        assert_eq!(Positive::INFINITY * 2.0, Positive::INFINITY);
+        assert_eq!((Positive::INFINITY * -2.0).to_dec(), SATURATED_FLOOR);
+        assert_eq!(Decimal::MIN - Positive::ONE, Decimal::MIN);
+        assert!(violation_counts().overflow >= before.overflow + 4);
+    }
+
+    #[test]
+    fn test_callback_then_saturate() {
+        static CALLS: AtomicUsize = AtomicUsize::new(0);
+        fn on_violation(violation: &Violation) {
+            assert_eq!(violation.kind, ViolationKind::Invariant);
+            assert_eq!(violation.operation, "sub");
+            CALLS.fetch_add(1, Ordering::Relaxed);
+        }
+
+        let _guard = scoped_policy(ViolationPolicy::Callback(on_violation));
+        let value = pos_or_panic!(1.0) - pos_or_panic!(3.0);
+        assert_eq!(value.to_dec(), SATURATED_FLOOR);
+        assert_eq!(CALLS.load(Ordering::Relaxed), 1);
+    }
+
+    #[test]
+    fn test_scoped_policy_nests_and_restores() {
+        {
+            let _outer = scoped_policy(ViolationPolicy::Saturate);
+            {
+                let _inner = scoped_policy(ViolationPolicy::Panic);
+                assert!(matches!(current_policy(), ViolationPolicy::Panic));
+            }
+            assert!(matches!(current_policy(), ViolationPolicy::Saturate));
+        }
+        assert!(matches!(current_policy(), ViolationPolicy::Panic));
+    }
+
+    #[test]
+    fn test_violation_display_matches_panic_messages() {
+        let overflow = Violation {
+            kind: ViolationKind::Overflow,
+            operation: "add",
+        };
+        assert_eq!(overflow.to_string(), "Positive arithmetic overflow in add");
+        let invariant = Violation {
+            kind: ViolationKind::Invariant,
+            operation: "sub",
+        };
+        assert_eq!(
+            invariant.to_string(),
+            "Positive invariant broken in sub: result would be non-positive"
+        );
+    }
+}
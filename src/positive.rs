@@ -6,21 +6,32 @@
 
 //! Core implementation of the Positive type.
 
-use crate::constants::{EPSILON, EPSILON_CMP};
-use crate::error::PositiveError;
-use approx::{AbsDiffEq, RelativeEq};
+use crate::constants::EPSILON;
+#[cfg(feature = "approx")]
+use crate::constants::EPSILON_CMP;
+use crate::error::{Operation, PositiveError};
+use crate::policy::ViolationKind;
+#[cfg(feature = "approx")]
+use crate::tolerance::Tolerance;
+use crate::tolerance::cmp_f64;
+use alloc::format;
+use alloc::string::String;
+#[cfg(feature = "approx")]
+use approx::{AbsDiffEq, RelativeEq, UlpsEq};
+use core::cmp::{Ordering, PartialEq};
+use core::fmt;
+use core::fmt::Display;
+#[cfg(not(feature = "non-zero"))]
+use core::iter::Sum;
+use core::ops::{Add, AddAssign, Div, Mul, MulAssign, Sub};
+use core::str::FromStr;
 use num_traits::{FromPrimitive, Pow, ToPrimitive};
 use rust_decimal::{Decimal, MathematicalOps, RoundingStrategy};
 use rust_decimal_macros::dec;
+#[cfg(feature = "serde")]
 use serde::de::Visitor;
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
-use std::cmp::{Ordering, PartialEq};
-use std::fmt;
-use std::fmt::Display;
-#[cfg(not(feature = "non-zero"))]
-use std::iter::Sum;
-use std::ops::{Add, AddAssign, Div, Mul, MulAssign, Sub};
-use std::str::FromStr;
 
 /// A wrapper type that represents a guaranteed positive decimal value.
 ///
@@ -30,8 +41,14 @@ use std::str::FromStr;
 /// When the `non-zero` feature is enabled, the value must be strictly
 /// greater than zero.
 #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(
+    feature = "diesel",
+    derive(diesel::expression::AsExpression, diesel::deserialize::FromSqlRow)
+)]
+#[cfg_attr(feature = "diesel", diesel(sql_type = diesel::sql_types::Numeric))]
+#[cfg_attr(feature = "diesel", diesel(sql_type = diesel::sql_types::Double))]
+#[cfg_attr(feature = "diesel", diesel(sql_type = diesel::sql_types::Text))]
 #[repr(transparent)]
-#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
 pub struct Positive(Decimal);
 
 /// Returns whether the given decimal value satisfies the positivity constraint.
@@ -53,26 +70,26 @@ pub fn is_valid_positive_value(value: Decimal) -> bool {
 
 /// Returns the minimum bound for error messages.
 ///
-/// Without the `non-zero` feature, the minimum is 0.0.
+/// Without the `non-zero` feature, the minimum is zero.
 /// With the `non-zero` feature, the minimum is the smallest representable
-/// positive f64 value.
+/// positive `Decimal` value.
 #[inline]
 #[must_use]
-fn min_bound() -> f64 {
+fn min_bound() -> Decimal {
     #[cfg(feature = "non-zero")]
     {
-        f64::MIN_POSITIVE
+        Decimal::new(1, 28)
     }
     #[cfg(not(feature = "non-zero"))]
     {
-        0.0
+        Decimal::ZERO
     }
 }
 
 /// Determines if the given type parameter `T` is the `Positive` type.
 #[must_use]
 pub fn is_positive<T: 'static>() -> bool {
-    std::any::TypeId::of::<T>() == std::any::TypeId::of::<Positive>()
+    core::any::TypeId::of::<T>() == core::any::TypeId::of::<Positive>()
 }
 
 /// Default rounding strategy used by every `Div` operator on `Positive`.
@@ -102,25 +119,91 @@ pub(crate) fn round_div(result: Decimal) -> Decimal {
     result.round_dp_with_strategy(DIV_SCALE, DIV_ROUNDING_STRATEGY)
 }
 
-/// Panics with a uniform message when a `Positive` arithmetic operation
-/// overflows the underlying `Decimal` range.
+/// Lower saturation bound for `Positive` results under
+/// [`ViolationPolicy::Saturate`](crate::policy::ViolationPolicy::Saturate):
+/// zero, or the smallest positive `Decimal` under the `non-zero` feature.
+#[cfg(not(feature = "non-zero"))]
+pub(crate) const SATURATED_FLOOR: Decimal = Decimal::ZERO;
+
+/// Lower saturation bound for `Positive` results under
+/// [`ViolationPolicy::Saturate`](crate::policy::ViolationPolicy::Saturate):
+/// zero, or the smallest positive `Decimal` under the `non-zero` feature.
+#[cfg(feature = "non-zero")]
+pub(crate) const SATURATED_FLOOR: Decimal = Decimal::from_parts(1, 0, 0, false, 28);
+
+/// Reports an overflow of a `Positive` arithmetic operation to the active
+/// [`ViolationPolicy`](crate::policy::ViolationPolicy) and returns
+/// `saturated` when the policy does not panic.
 ///
+/// Under the default policy this panics with a uniform message.
 /// Marked `#[cold]` and `#[inline(never)]` so the happy path stays lean.
 #[cold]
 #[inline(never)]
-pub(crate) fn overflow_panic(op: &'static str) -> ! {
-    panic!("Positive arithmetic overflow in {op}")
+pub(crate) fn overflow_violation(op: &'static str, saturated: Decimal) -> Decimal {
+    crate::policy::report(ViolationKind::Overflow, op);
+    saturated
 }
 
-/// Panics with a uniform message when the result of a `Positive`
-/// arithmetic operation would violate the positivity invariant
-/// (negative, or zero under the `non-zero` feature).
+/// Reports that the result of a `Positive` arithmetic operation would
+/// violate the positivity invariant (negative, or zero under the
+/// `non-zero` feature) and returns `saturated` when the active
+/// [`ViolationPolicy`](crate::policy::ViolationPolicy) does not panic.
 ///
+/// Under the default policy this panics with a uniform message.
 /// Marked `#[cold]` and `#[inline(never)]` so the happy path stays lean.
 #[cold]
 #[inline(never)]
-pub(crate) fn invariant_panic(op: &'static str) -> ! {
-    panic!("Positive invariant broken in {op}: result would be non-positive")
+pub(crate) fn invariant_broken(op: &'static str, saturated: Decimal) -> Decimal {
+    crate::policy::report(ViolationKind::Invariant, op);
+    saturated
+}
+
+/// Saturation bound for a `Positive` result scaled by `factor`: the
+/// result heads towards zero when `factor` is negative.
+#[inline]
+fn positive_saturation(factor: Decimal) -> Decimal {
+    if factor.is_sign_negative() {
+        SATURATED_FLOOR
+    } else {
+        Decimal::MAX
+    }
+}
+
+/// Saturation bound for a signed `Decimal` result with the sign of `value`.
+#[inline]
+fn decimal_saturation(value: Decimal) -> Decimal {
+    if value.is_sign_negative() {
+        Decimal::MIN
+    } else {
+        Decimal::MAX
+    }
+}
+
+/// Panics with a uniform message when the `debug-invariants` feature
+/// detects a `Positive` holding a value that violates the invariant.
+///
+/// Marked `#[cold]` and `#[inline(never)]` so the happy path stays lean.
+#[cfg(feature = "debug-invariants")]
+#[cold]
+#[inline(never)]
+#[track_caller]
+pub(crate) fn debug_invariant_panic(op: &'static str, value: Decimal) -> ! {
+    panic!("Positive invariant violated in {op}: {value} is not a valid positive value")
+}
+
+/// `const`-compatible version of [`is_valid_positive_value`], used where
+/// `Decimal` comparisons are not available.
+#[cfg(feature = "debug-invariants")]
+#[inline]
+const fn is_valid_positive_value_const(value: &Decimal) -> bool {
+    #[cfg(feature = "non-zero")]
+    {
+        !value.is_zero() && !value.is_sign_negative()
+    }
+    #[cfg(not(feature = "non-zero"))]
+    {
+        value.is_zero() || !value.is_sign_negative()
+    }
 }
 
 impl Positive {
@@ -238,16 +321,16 @@ impl Positive {
         let dec = Decimal::from_f64(value);
         match dec {
             Some(value) if is_valid_positive_value(value) => Ok(Positive(value)),
-            Some(value) => Err(PositiveError::OutOfBounds {
-                value: value.to_f64().unwrap_or(0.0),
-                min: min_bound(),
-                max: f64::MAX,
-            }),
-            None => Err(PositiveError::ConversionError {
-                from_type: "f64".to_string(),
-                to_type: "Positive".to_string(),
-                reason: "failed to parse Decimal".to_string(),
-            }),
+            Some(value) => Err(PositiveError::out_of_bounds(
+                value,
+                min_bound(),
+                Decimal::MAX,
+            )),
+            None => Err(PositiveError::conversion_error(
+                "f64",
+                "Positive",
+                "value not representable as Decimal",
+            )),
         }
     }
 
@@ -260,11 +343,11 @@ impl Positive {
         if is_valid_positive_value(value) {
             Ok(Positive(value))
         } else {
-            Err(PositiveError::OutOfBounds {
-                value: value.to_f64().unwrap_or(0.0),
-                min: min_bound(),
-                max: f64::INFINITY,
-            })
+            Err(PositiveError::out_of_bounds(
+                value,
+                min_bound(),
+                Decimal::MAX,
+            ))
         }
     }
 
@@ -388,55 +471,58 @@ impl Positive {
     /// Rounds the value down to the nearest integer.
     #[must_use]
     pub fn floor(&self) -> Positive {
-        Positive(self.0.floor())
+        Positive::from_op(self.0.floor(), "floor")
     }
 
     /// Raises this value to an integer power.
     #[must_use]
     pub fn powi(&self, n: i64) -> Positive {
-        Positive(self.0.powi(n))
+        Positive::from_op(self.0.powi(n), "powi")
     }
 
     /// Computes the result of raising the current value to the power of the given exponent.
     #[must_use]
     pub fn pow(&self, n: Positive) -> Positive {
-        Positive(self.0.pow(n.to_dec()))
+        Positive::from_op(self.0.pow(n.to_dec()), "pow")
     }
 
     /// Raises the current value to the power of `n` using unsigned integer exponentiation.
     #[must_use]
     pub fn powu(&self, n: u64) -> Positive {
-        Positive(self.0.powu(n))
+        Positive::from_op(self.0.powu(n), "powu")
     }
 
     /// Raises this value to a decimal power.
     #[must_use]
     pub fn powd(&self, p0: Decimal) -> Positive {
-        Positive(self.0.powd(p0))
+        Positive::from_op(self.0.powd(p0), "powd")
     }
 
     /// Rounds the value to the nearest integer.
     #[must_use]
     pub fn round(&self) -> Positive {
-        Positive(self.0.round())
+        Positive::from_op(self.0.round(), "round")
     }
 
     /// Rounds the current value to a "nice" number, based on its magnitude.
     #[must_use]
     pub fn round_to_nice_number(&self) -> Positive {
-        let magnitude = self.log10().floor();
-        let ten_pow = Positive::TEN.pow(magnitude);
-        let normalized = self / &ten_pow;
+        // Work on the raw `Decimal` so intermediate values (a negative
+        // magnitude for inputs below one, or a zero magnitude under the
+        // `non-zero` feature) never pass through a `Positive`.
+        let magnitude = self.0.log10().floor();
+        let ten_pow = Decimal::TEN.powd(magnitude);
+        let normalized = self.0 / ten_pow;
         let nice_number = if normalized < dec!(1.5) {
-            Positive::ONE
-        } else if normalized < pos_or_panic!(3.0) {
-            Positive::TWO
-        } else if normalized < pos_or_panic!(7.0) {
-            pos_or_panic!(5.0)
+            Decimal::ONE
+        } else if normalized < dec!(3) {
+            Decimal::TWO
+        } else if normalized < dec!(7) {
+            dec!(5)
         } else {
-            Positive::TEN
+            Decimal::TEN
         };
-        nice_number * pos_or_panic!(10.0).powu(magnitude.to_u64())
+        Positive::from_op(nice_number * ten_pow, "round_to_nice_number")
     }
 
     /// Calculates the square root of the value.
@@ -447,28 +533,34 @@ impl Positive {
     /// Use `sqrt_checked()` for a non-panicking alternative.
     #[must_use]
     pub fn sqrt(&self) -> Positive {
-        Positive(self.0.sqrt().expect("Square root calculation failed"))
+        Positive::from_op(
+            self.0.sqrt().expect("Square root calculation failed"),
+            "sqrt",
+        )
     }
 
     /// Calculates the square root, returning an error if it fails.
     pub fn sqrt_checked(&self) -> Result<Positive, PositiveError> {
-        self.0.sqrt().map(Positive).ok_or_else(|| {
-            PositiveError::arithmetic_error("sqrt", "square root calculation failed")
-        })
+        self.0
+            .sqrt()
+            .map(|value| Positive::from_op(value, "sqrt_checked"))
+            .ok_or_else(|| {
+                PositiveError::arithmetic_error(Operation::Sqrt, "square root calculation failed")
+            })
     }
 
     /// Calculates the natural logarithm of the value.
     #[inline]
     #[must_use]
     pub fn ln(&self) -> Positive {
-        Positive(self.0.ln())
+        Positive::from_op(self.0.ln(), "ln")
     }
 
     /// Rounds the value to a specified number of decimal places.
     #[inline]
     #[must_use]
     pub fn round_to(&self, decimal_places: u32) -> Positive {
-        Positive(self.0.round_dp(decimal_places))
+        Positive::from_op(self.0.round_dp(decimal_places), "round_to")
     }
 
     /// Formats the value with a fixed number of decimal places.
@@ -491,7 +583,7 @@ impl Positive {
     #[inline]
     #[must_use]
     pub fn exp(&self) -> Positive {
-        Positive(self.0.exp())
+        Positive::from_op(self.0.exp(), "exp")
     }
 
     /// Clamps the value between a minimum and maximum.
@@ -506,6 +598,33 @@ impl Positive {
         }
     }
 
+    /// Returns one unit in the last place of this value's `Decimal` scale.
+    ///
+    /// For example `1.25` has scale 2, so its ulp is `0.01`. Useful as a
+    /// scale-aware epsilon for the `approx` comparison macros.
+    #[inline]
+    #[must_use]
+    pub fn ulp(&self) -> Positive {
+        Positive::from_op(Decimal::new(1, self.0.scale()), "ulp")
+    }
+
+    /// Returns `10^-decimal_places` as a `Positive`, for use as an epsilon.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `InvalidPrecision` error if `decimal_places` exceeds the
+    /// 28 digits supported by `Decimal`.
+    pub fn epsilon(decimal_places: u32) -> Result<Positive, PositiveError> {
+        Decimal::try_new(1, decimal_places)
+            .map(|value| Positive::from_op(value, "epsilon"))
+            .map_err(|_| {
+                PositiveError::invalid_precision(
+                    i32::try_from(decimal_places).unwrap_or(i32::MAX),
+                    "scale exceeds the maximum of 28 decimal places",
+                )
+            })
+    }
+
     /// Checks if the value is exactly zero.
     #[inline]
     #[must_use]
@@ -517,14 +636,14 @@ impl Positive {
     #[inline]
     #[must_use]
     pub fn ceiling(&self) -> Positive {
-        Positive(self.to_dec().ceil())
+        Positive::from_op(self.to_dec().ceil(), "ceiling")
     }
 
     /// Computes the base-10 logarithm of the value.
     #[inline]
     #[must_use]
     pub fn log10(&self) -> Positive {
-        Positive(self.0.log10())
+        Positive::from_op(self.0.log10(), "log10")
     }
 
     /// Subtracts a decimal value, returning zero if the result would be negative.
@@ -535,9 +654,9 @@ impl Positive {
     #[must_use]
     pub fn sub_or_zero(&self, other: &Decimal) -> Positive {
         if &self.0 > other {
-            Positive(self.0 - other)
+            Positive::from_op(self.0 - other, "sub_or_zero")
         } else {
-            Positive(Decimal::ZERO)
+            Positive::from_op(Decimal::ZERO, "sub_or_zero")
         }
     }
 
@@ -545,7 +664,7 @@ impl Positive {
     #[must_use]
     pub fn sub_or_none(&self, other: &Decimal) -> Option<Positive> {
         if &self.0 >= other {
-            Some(Positive(self.0 - other))
+            Some(Positive::from_op(self.0 - other, "sub_or_none"))
         } else {
             None
         }
@@ -565,12 +684,96 @@ impl Positive {
     #[must_use]
     pub fn saturating_sub(&self, rhs: &Self) -> Self {
         if self.0 > rhs.0 {
-            Positive(self.0 - rhs.0)
+            Positive::from_op(self.0 - rhs.0, "saturating_sub")
         } else {
             Positive::ZERO
         }
     }
 
+    /// Returns the absolute difference `|self - other|`.
+    ///
+    /// This method is not available when the `non-zero` feature is enabled
+    /// because the result is zero when both values are equal.
+    #[cfg(not(feature = "non-zero"))]
+    #[inline]
+    #[must_use]
+    pub fn abs_diff(&self, other: &Positive) -> Positive {
+        if self.0 >= other.0 {
+            Positive::from_op(self.0 - other.0, "abs_diff")
+        } else {
+            Positive::from_op(other.0 - self.0, "abs_diff")
+        }
+    }
+
+    /// Returns the signed difference `self - other` as a `Decimal`.
+    ///
+    /// Unlike `Sub for Positive`, this never panics: a negative result is
+    /// returned as a negative `Decimal`.
+    #[inline]
+    #[must_use]
+    pub fn signed_sub(&self, other: &Positive) -> Decimal {
+        self.0 - other.0
+    }
+
+    /// Returns the relative change from `self` to `to`, `(to - self) / self`,
+    /// as a signed fraction (`0.05` for a 5% increase).
+    ///
+    /// # Errors
+    ///
+    /// Returns an `ArithmeticError` if `self` is zero or the change
+    /// overflows.
+    #[must_use = "checked arithmetic returns a Result; ignoring it silences the error"]
+    pub fn pct_change(&self, to: &Positive) -> Result<Decimal, PositiveError> {
+        if self.is_zero() {
+            return Err(PositiveError::arithmetic_error(
+                Operation::PctChange,
+                "division by zero",
+            ));
+        }
+        let change = (to.0 - self.0)
+            .checked_div(self.0)
+            .ok_or_else(|| PositiveError::arithmetic_error(Operation::PctChange, "overflow"))?;
+        Ok(round_div(change))
+    }
+
+    /// Returns the relative difference `|self - other| / max(self, other)`.
+    ///
+    /// The result lies in `[0, 1]` and is symmetric in its arguments.
+    /// Returns zero when both values are zero.
+    #[must_use]
+    pub fn relative_diff(&self, other: &Positive) -> Decimal {
+        let largest = self.0.max(other.0);
+        if largest.is_zero() {
+            return Decimal::ZERO;
+        }
+        round_div((self.0 - other.0).abs() / largest)
+    }
+
+    /// Returns the logarithmic return `ln(to / self)` as a signed `Decimal`.
+    ///
+    /// Unlike [`Positive::ln`], the result may be negative (when `to < self`),
+    /// so it is never wrapped in a `Positive`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `ArithmeticError` if either value is zero or the ratio
+    /// overflows.
+    #[must_use = "checked arithmetic returns a Result; ignoring it silences the error"]
+    pub fn log_return(&self, to: &Positive) -> Result<Decimal, PositiveError> {
+        if self.is_zero() || to.is_zero() {
+            return Err(PositiveError::arithmetic_error(
+                Operation::LogReturn,
+                "logarithm of zero",
+            ));
+        }
+        let ratio =
+            to.0.checked_div(self.0)
+                .ok_or_else(|| PositiveError::arithmetic_error(Operation::LogReturn, "overflow"))?;
+        ratio.checked_ln().ok_or_else(|| {
+            PositiveError::arithmetic_error(Operation::LogReturn, "logarithm failed")
+        })
+    }
+
     /// Checked division that returns Result instead of panicking.
     ///
     /// Uses [`DIV_ROUNDING_STRATEGY`] (banker's rounding) for any
@@ -581,11 +784,11 @@ impl Positive {
     pub fn checked_div(&self, rhs: &Self) -> Result<Self, PositiveError> {
         if rhs.is_zero() {
             Err(PositiveError::arithmetic_error(
-                "division",
+                Operation::Div,
                 "division by zero",
             ))
         } else {
-            Ok(Positive(round_div(self.0 / rhs.0)))
+            Ok(Positive::from_op(round_div(self.0 / rhs.0), "checked_div"))
         }
     }
 
@@ -602,15 +805,18 @@ impl Positive {
     ) -> Result<Self, PositiveError> {
         if rhs.is_zero() {
             return Err(PositiveError::arithmetic_error(
-                "division",
+                Operation::Div,
                 "division by zero",
             ));
         }
         let result = self
             .0
             .checked_div(rhs.0)
-            .ok_or_else(|| PositiveError::arithmetic_error("division", "overflow"))?;
-        Ok(Positive(result.round_dp_with_strategy(DIV_SCALE, strategy)))
+            .ok_or_else(|| PositiveError::arithmetic_error(Operation::Div, "overflow"))?;
+        Ok(Positive::from_op(
+            result.round_dp_with_strategy(DIV_SCALE, strategy),
+            "checked_div_with_strategy",
+        ))
     }
 
     /// Checked addition with an `f64`, returning a `Result` instead of panicking.
@@ -629,7 +835,7 @@ impl Positive {
         let result = self
             .0
             .checked_add(rhs_dec)
-            .ok_or_else(|| PositiveError::arithmetic_error("add_f64", "overflow"))?;
+            .ok_or_else(|| PositiveError::arithmetic_error(Operation::Add, "overflow"))?;
         Positive::new_decimal(result)
     }
 
@@ -648,7 +854,7 @@ impl Positive {
         let result = self
             .0
             .checked_sub(rhs_dec)
-            .ok_or_else(|| PositiveError::arithmetic_error("sub_f64", "overflow"))?;
+            .ok_or_else(|| PositiveError::arithmetic_error(Operation::Sub, "overflow"))?;
         Positive::new_decimal(result)
     }
 
@@ -668,7 +874,7 @@ impl Positive {
         let result = self
             .0
             .checked_mul(rhs_dec)
-            .ok_or_else(|| PositiveError::arithmetic_error("mul_f64", "overflow"))?;
+            .ok_or_else(|| PositiveError::arithmetic_error(Operation::Mul, "overflow"))?;
         Positive::new_decimal(result)
     }
 
@@ -687,14 +893,14 @@ impl Positive {
         })?;
         if rhs_dec.is_zero() {
             return Err(PositiveError::arithmetic_error(
-                "div_f64",
+                Operation::Div,
                 "division by zero",
             ));
         }
         let result = self
             .0
             .checked_div(rhs_dec)
-            .ok_or_else(|| PositiveError::arithmetic_error("div_f64", "overflow"))?;
+            .ok_or_else(|| PositiveError::arithmetic_error(Operation::Div, "overflow"))?;
         Positive::new_decimal(round_div(result))
     }
 
@@ -783,7 +989,8 @@ impl Positive {
     ///
     /// `new_unchecked` performs **no** validation, **no** conversion,
     /// **no** rounding: the returned value wraps exactly the `Decimal`
-    /// you pass in.
+    /// you pass in. The one exception is the `debug-invariants` feature,
+    /// under which the invariant is re-asserted and a violation panics.
     ///
     /// # When to use
     ///
@@ -829,10 +1036,65 @@ impl Positive {
     /// ```
     #[inline]
     #[must_use]
+    #[track_caller]
     pub const unsafe fn new_unchecked(value: Decimal) -> Self {
+        #[cfg(feature = "debug-invariants")]
+        if !is_valid_positive_value_const(&value) {
+            panic!(
+                "Positive invariant violated in new_unchecked: value is not a valid positive value"
+            );
+        }
+        Positive(value)
+    }
+
+    /// Wraps a `Decimal` produced by the operation `op`.
+    ///
+    /// With the `debug-invariants` feature enabled, re-asserts the
+    /// positivity invariant and panics naming `op` if it does not hold.
+    /// Without the feature this compiles down to the bare constructor.
+    #[inline]
+    #[track_caller]
+    pub(crate) fn from_op(value: Decimal, op: &'static str) -> Self {
+        #[cfg(feature = "debug-invariants")]
+        if !is_valid_positive_value(value) {
+            debug_invariant_panic(op, value);
+        }
+        #[cfg(not(feature = "debug-invariants"))]
+        let _ = op;
         Positive(value)
     }
 
+    /// Returns whether the wrapped value satisfies the positivity invariant.
+    ///
+    /// Always `true` for values built through the validated constructors;
+    /// `false` indicates misuse of [`Positive::new_unchecked`] or a method
+    /// whose result left the valid range (for example [`Positive::ln`] of a
+    /// value below one).
+    #[inline]
+    #[must_use]
+    pub fn is_valid(&self) -> bool {
+        is_valid_positive_value(self.0)
+    }
+
+    /// Panics if the wrapped value violates the positivity invariant.
+    ///
+    /// Intended for downstream code that wants to check values received
+    /// from `unsafe` or foreign sources at a boundary.
+    ///
+    /// # Panics
+    ///
+    /// Panics when [`Positive::is_valid`] returns `false`.
+    #[inline]
+    #[track_caller]
+    pub fn assert_invariant(&self) {
+        if !self.is_valid() {
+            panic!(
+                "Positive invariant violated: {} is not a valid positive value",
+                self.0
+            );
+        }
+    }
+
     /// Crate-private const constructor used exclusively by `crate::constants`
     /// to define `Positive` constants in `const` context. The invariant is
     /// enforced by the callers: every constant in `crate::constants` is a
@@ -894,28 +1156,28 @@ impl From<Positive> for usize {
 impl PartialEq<&Positive> for f64 {
     #[inline]
     fn eq(&self, other: &&Positive) -> bool {
-        self == &other.0.to_f64().unwrap_or(0.0)
+        cmp_f64(other.0, *self) == Some(Ordering::Equal)
     }
 }
 
 impl PartialOrd<&Positive> for f64 {
     #[inline]
     fn partial_cmp(&self, other: &&Positive) -> Option<Ordering> {
-        self.partial_cmp(&other.0.to_f64().unwrap_or(0.0))
+        cmp_f64(other.0, *self).map(Ordering::reverse)
     }
 }
 
 impl PartialEq<Positive> for f64 {
     #[inline]
     fn eq(&self, other: &Positive) -> bool {
-        self == &other.0.to_f64().unwrap_or(0.0)
+        cmp_f64(other.0, *self) == Some(Ordering::Equal)
     }
 }
 
 impl PartialOrd<Positive> for f64 {
     #[inline]
     fn partial_cmp(&self, other: &Positive) -> Option<Ordering> {
-        self.partial_cmp(&other.0.to_f64().unwrap_or(0.0))
+        cmp_f64(other.0, *self).map(Ordering::reverse)
     }
 }
 
@@ -952,12 +1214,11 @@ impl Add<Positive> for f64 {
 }
 
 impl FromStr for Positive {
-    type Err = String;
+    type Err = PositiveError;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s.parse::<Decimal>() {
-            Ok(value) if is_valid_positive_value(value) => Ok(Positive(value)),
-            Ok(value) => Err(format!("Value must be positive, got {value}")),
-            Err(e) => Err(format!("Failed to parse as Decimal: {e}")),
+            Ok(value) => Positive::new_decimal(value),
+            Err(e) => Err(PositiveError::parse_error(s, e)),
         }
     }
 }
@@ -1051,15 +1312,18 @@ impl Mul<f64> for Positive {
     type Output = Positive;
     #[inline]
     fn mul(self, rhs: f64) -> Positive {
-        let rhs_dec = Decimal::from_f64(rhs).unwrap_or_else(|| invariant_panic("mul_f64"));
+        let rhs_dec = match Decimal::from_f64(rhs) {
+            Some(v) => v,
+            None => return Positive(invariant_broken("mul_f64", SATURATED_FLOOR)),
+        };
         let result = match self.0.checked_mul(rhs_dec) {
             Some(v) => v,
-            None => overflow_panic("mul_f64"),
+            None => overflow_violation("mul_f64", positive_saturation(rhs_dec)),
         };
         if is_valid_positive_value(result) {
             Positive(result)
         } else {
-            invariant_panic("mul_f64")
+            Positive(invariant_broken("mul_f64", SATURATED_FLOOR))
         }
     }
 }
@@ -1072,18 +1336,21 @@ impl Div<f64> for Positive {
     /// `Decimal`.
     #[inline]
     fn div(self, rhs: f64) -> Positive {
-        let rhs_dec = Decimal::from_f64(rhs).unwrap_or_else(|| invariant_panic("div_f64"));
+        let rhs_dec = match Decimal::from_f64(rhs) {
+            Some(v) => v,
+            None => return Positive(invariant_broken("div_f64", SATURATED_FLOOR)),
+        };
         if rhs_dec.is_zero() {
-            invariant_panic("div_f64");
+            return Positive(invariant_broken("div_f64", Decimal::MAX));
         }
         let result = match self.0.checked_div(rhs_dec) {
             Some(v) => round_div(v),
-            None => overflow_panic("div_f64"),
+            None => overflow_violation("div_f64", positive_saturation(rhs_dec)),
         };
         if is_valid_positive_value(result) {
             Positive(result)
         } else {
-            invariant_panic("div_f64")
+            Positive(invariant_broken("div_f64", SATURATED_FLOOR))
         }
     }
 }
@@ -1094,18 +1361,21 @@ impl Div<f64> for &Positive {
     /// (banker's rounding) when rounding is required.
     #[inline]
     fn div(self, rhs: f64) -> Positive {
-        let rhs_dec = Decimal::from_f64(rhs).unwrap_or_else(|| invariant_panic("div_f64"));
+        let rhs_dec = match Decimal::from_f64(rhs) {
+            Some(v) => v,
+            None => return Positive(invariant_broken("div_f64", SATURATED_FLOOR)),
+        };
         if rhs_dec.is_zero() {
-            invariant_panic("div_f64");
+            return Positive(invariant_broken("div_f64", Decimal::MAX));
         }
         let result = match self.0.checked_div(rhs_dec) {
             Some(v) => round_div(v),
-            None => overflow_panic("div_f64"),
+            None => overflow_violation("div_f64", positive_saturation(rhs_dec)),
         };
         if is_valid_positive_value(result) {
             Positive(result)
         } else {
-            invariant_panic("div_f64")
+            Positive(invariant_broken("div_f64", SATURATED_FLOOR))
         }
     }
 }
@@ -1114,15 +1384,18 @@ impl Sub<f64> for Positive {
     type Output = Positive;
     #[inline]
     fn sub(self, rhs: f64) -> Self::Output {
-        let rhs_dec = Decimal::from_f64(rhs).unwrap_or_else(|| invariant_panic("sub_f64"));
+        let rhs_dec = match Decimal::from_f64(rhs) {
+            Some(v) => v,
+            None => return Positive(invariant_broken("sub_f64", SATURATED_FLOOR)),
+        };
         let result = match self.0.checked_sub(rhs_dec) {
             Some(v) => v,
-            None => overflow_panic("sub_f64"),
+            None => overflow_violation("sub_f64", SATURATED_FLOOR),
         };
         if is_valid_positive_value(result) {
             Positive(result)
         } else {
-            invariant_panic("sub_f64")
+            Positive(invariant_broken("sub_f64", SATURATED_FLOOR))
         }
     }
 }
@@ -1131,15 +1404,18 @@ impl Add<f64> for Positive {
     type Output = Positive;
     #[inline]
     fn add(self, rhs: f64) -> Self::Output {
-        let rhs_dec = Decimal::from_f64(rhs).unwrap_or_else(|| invariant_panic("add_f64"));
+        let rhs_dec = match Decimal::from_f64(rhs) {
+            Some(v) => v,
+            None => return Positive(invariant_broken("add_f64", SATURATED_FLOOR)),
+        };
         let result = match self.0.checked_add(rhs_dec) {
             Some(v) => v,
-            None => overflow_panic("add_f64"),
+            None => overflow_violation("add_f64", Decimal::MAX),
         };
         if is_valid_positive_value(result) {
             Positive(result)
         } else {
-            invariant_panic("add_f64")
+            Positive(invariant_broken("add_f64", SATURATED_FLOOR))
         }
     }
 }
@@ -1147,28 +1423,28 @@ impl Add<f64> for Positive {
 impl PartialOrd<f64> for Positive {
     #[inline]
     fn partial_cmp(&self, other: &f64) -> Option<Ordering> {
-        self.0.to_f64().unwrap_or(0.0).partial_cmp(other)
+        cmp_f64(self.0, *other)
     }
 }
 
 impl PartialEq<f64> for &Positive {
     #[inline]
     fn eq(&self, other: &f64) -> bool {
-        self.0.to_f64().unwrap_or(0.0) == *other
+        cmp_f64(self.0, *other) == Some(Ordering::Equal)
     }
 }
 
 impl PartialOrd<f64> for &Positive {
     #[inline]
     fn partial_cmp(&self, other: &f64) -> Option<Ordering> {
-        self.0.to_f64().unwrap_or(0.0).partial_cmp(other)
+        cmp_f64(self.0, *other)
     }
 }
 
 impl PartialEq<f64> for Positive {
     #[inline]
     fn eq(&self, other: &f64) -> bool {
-        self.to_f64() == *other
+        cmp_f64(self.0, *other) == Some(Ordering::Equal)
     }
 }
 
@@ -1200,10 +1476,12 @@ impl fmt::Debug for Positive {
     }
 }
 
+// Comparisons against `Decimal` and `f64` are exact in both directions.
+// See `crate::tolerance` for explicit-tolerance alternatives.
 impl PartialEq<Decimal> for Positive {
     #[inline]
     fn eq(&self, other: &Decimal) -> bool {
-        (self.0 - *other).abs() <= EPSILON_CMP
+        self.0 == *other
     }
 }
 
@@ -1217,11 +1495,17 @@ impl PartialEq<Decimal> for Positive {
 // Switching to `#[serde(transparent)]` would change the wire format
 // and is therefore deferred. Duplicated validation inside the
 // deserialiser is removed separately in #27.
+// Binary formats (`is_human_readable() == false`) use the exact compact
+// encoding in `crate::binary` instead.
+#[cfg(feature = "serde")]
 impl Serialize for Positive {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: Serializer,
     {
+        if !serializer.is_human_readable() {
+            return crate::binary::serialize_compact(self, serializer);
+        }
         if *self == Positive::INFINITY {
             return serializer.serialize_f64(f64::MAX);
         }
@@ -1241,6 +1525,7 @@ impl Serialize for Positive {
     }
 }
 
+#[cfg(feature = "serde")]
 impl<'de> Deserialize<'de> for Positive {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -1259,8 +1544,10 @@ impl<'de> Deserialize<'de> for Positive {
             where
                 E: serde::de::Error,
             {
-                Err(serde::de::Error::custom(format!(
-                    "Invalid string: '{value}'. Expected a positive number."
+                Err(crate::de::custom(PositiveError::conversion_error(
+                    "string",
+                    "Positive",
+                    &format!("invalid string '{value}', expected a positive number"),
                 )))
             }
 
@@ -1268,14 +1555,14 @@ impl<'de> Deserialize<'de> for Positive {
             where
                 E: serde::de::Error,
             {
-                Positive::new_decimal(Decimal::from(value)).map_err(serde::de::Error::custom)
+                Positive::new_decimal(Decimal::from(value)).map_err(crate::de::custom)
             }
 
             fn visit_u64<E>(self, value: u64) -> Result<Self::Value, E>
             where
                 E: serde::de::Error,
             {
-                Positive::new_decimal(Decimal::from(value)).map_err(serde::de::Error::custom)
+                Positive::new_decimal(Decimal::from(value)).map_err(crate::de::custom)
             }
 
             fn visit_f64<E>(self, value: f64) -> Result<Self::Value, E>
@@ -1288,13 +1575,88 @@ impl<'de> Deserialize<'de> for Positive {
                 if value == f64::MAX {
                     return Ok(Positive::INFINITY);
                 }
-                let decimal = Decimal::from_f64(value)
-                    .ok_or_else(|| serde::de::Error::custom("Failed to convert f64 to Decimal"))?;
-                Positive::new_decimal(decimal).map_err(serde::de::Error::custom)
+                let decimal = Decimal::from_f64(value).ok_or_else(|| {
+                    crate::de::custom(PositiveError::conversion_error(
+                        "f64",
+                        "Decimal",
+                        "value not representable as Decimal",
+                    ))
+                })?;
+                Positive::new_decimal(decimal).map_err(crate::de::custom)
             }
         }
 
-        deserializer.deserialize_any(PositiveVisitor)
+        if !deserializer.is_human_readable() {
+            return crate::binary::deserialize_compact(deserializer)
+                .map(|value| Positive::from_op(value.0, "deserialize"));
+        }
+        deserializer
+            .deserialize_any(PositiveVisitor)
+            .map(|value| Positive::from_op(value.0, "deserialize"))
+    }
+}
+
+// The schemas are hand-written to describe the wire format produced by
+// the `Serialize` impl above (a JSON number, never the string `Decimal`
+// would derive) and to carry the positivity bound.
+#[cfg(any(feature = "utoipa", feature = "schemars"))]
+const SCHEMA_DESCRIPTION: &str = "A non-negative decimal number (strictly positive with the \
+     `non-zero` feature). Integer values are emitted without a fractional part; \
+     `Positive::INFINITY` is emitted as the largest `f64` (1.7976931348623157e308).";
+
+#[cfg(any(feature = "utoipa", feature = "schemars"))]
+const SCHEMA_EXAMPLE: f64 = 12.345;
+
+#[cfg(feature = "utoipa")]
+impl utoipa::PartialSchema for Positive {
+    fn schema() -> utoipa::openapi::RefOr<utoipa::openapi::schema::Schema> {
+        use utoipa::openapi::schema::{ObjectBuilder, Type};
+
+        let builder = ObjectBuilder::new()
+            .schema_type(Type::Number)
+            .description(Some(SCHEMA_DESCRIPTION))
+            .examples([SCHEMA_EXAMPLE]);
+        #[cfg(feature = "non-zero")]
+        let builder = builder.exclusive_minimum(Some(0));
+        #[cfg(not(feature = "non-zero"))]
+        let builder = builder.minimum(Some(0));
+        builder.into()
+    }
+}
+
+#[cfg(feature = "utoipa")]
+impl utoipa::ToSchema for Positive {
+    fn name() -> std::borrow::Cow<'static, str> {
+        std::borrow::Cow::Borrowed("Positive")
+    }
+}
+
+#[cfg(feature = "schemars")]
+impl schemars::JsonSchema for Positive {
+    fn schema_name() -> std::borrow::Cow<'static, str> {
+        std::borrow::Cow::Borrowed("Positive")
+    }
+
+    fn schema_id() -> std::borrow::Cow<'static, str> {
+        std::borrow::Cow::Borrowed("positive::Positive")
+    }
+
+    fn json_schema(_generator: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        #[cfg(feature = "non-zero")]
+        let schema = schemars::json_schema!({
+            "type": "number",
+            "exclusiveMinimum": 0,
+            "description": SCHEMA_DESCRIPTION,
+            "examples": [SCHEMA_EXAMPLE],
+        });
+        #[cfg(not(feature = "non-zero"))]
+        let schema = schemars::json_schema!({
+            "type": "number",
+            "minimum": 0,
+            "description": SCHEMA_DESCRIPTION,
+            "examples": [SCHEMA_EXAMPLE],
+        });
+        schema
     }
 }
 
@@ -1303,8 +1665,8 @@ impl Add for Positive {
     #[inline]
     fn add(self, other: Positive) -> Positive {
         match self.0.checked_add(other.0) {
-            Some(v) => Positive(v),
-            None => overflow_panic("add"),
+            Some(v) => Positive::from_op(v, "add"),
+            None => Positive(overflow_violation("add", Decimal::MAX)),
         }
     }
 }
@@ -1315,12 +1677,12 @@ impl Sub for Positive {
     fn sub(self, rhs: Self) -> Self::Output {
         let result = match self.0.checked_sub(rhs.0) {
             Some(v) => v,
-            None => overflow_panic("sub"),
+            None => overflow_violation("sub", SATURATED_FLOOR),
         };
         if is_valid_positive_value(result) {
             Positive(result)
         } else {
-            invariant_panic("sub")
+            Positive(invariant_broken("sub", SATURATED_FLOOR))
         }
     }
 }
@@ -1333,11 +1695,11 @@ impl Div for Positive {
     #[inline]
     fn div(self, other: Positive) -> Self::Output {
         if other.0.is_zero() {
-            invariant_panic("div");
+            return Positive(invariant_broken("div", Decimal::MAX));
         }
         match self.0.checked_div(other.0) {
-            Some(v) => Positive(round_div(v)),
-            None => overflow_panic("div"),
+            Some(v) => Positive::from_op(round_div(v), "div"),
+            None => Positive(overflow_violation("div", Decimal::MAX)),
         }
     }
 }
@@ -1349,11 +1711,11 @@ impl Div for &Positive {
     #[inline]
     fn div(self, other: &Positive) -> Self::Output {
         if other.0.is_zero() {
-            invariant_panic("div");
+            return Positive(invariant_broken("div", Decimal::MAX));
         }
         match self.0.checked_div(other.0) {
-            Some(v) => Positive(round_div(v)),
-            None => overflow_panic("div"),
+            Some(v) => Positive::from_op(round_div(v), "div"),
+            None => Positive(overflow_violation("div", Decimal::MAX)),
         }
     }
 }
@@ -1364,12 +1726,12 @@ impl Add<Decimal> for Positive {
     fn add(self, rhs: Decimal) -> Positive {
         let result = match self.0.checked_add(rhs) {
             Some(v) => v,
-            None => overflow_panic("add_decimal"),
+            None => overflow_violation("add_decimal", Decimal::MAX),
         };
         if is_valid_positive_value(result) {
             Positive(result)
         } else {
-            invariant_panic("add_decimal")
+            Positive(invariant_broken("add_decimal", SATURATED_FLOOR))
         }
     }
 }
@@ -1380,12 +1742,12 @@ impl Add<&Decimal> for Positive {
     fn add(self, rhs: &Decimal) -> Self::Output {
         let result = match self.0.checked_add(*rhs) {
             Some(v) => v,
-            None => overflow_panic("add_decimal"),
+            None => overflow_violation("add_decimal", Decimal::MAX),
         };
         if is_valid_positive_value(result) {
             Positive(result)
         } else {
-            invariant_panic("add_decimal")
+            Positive(invariant_broken("add_decimal", SATURATED_FLOOR))
         }
     }
 }
@@ -1396,12 +1758,12 @@ impl Sub<Decimal> for Positive {
     fn sub(self, rhs: Decimal) -> Positive {
         let result = match self.0.checked_sub(rhs) {
             Some(v) => v,
-            None => overflow_panic("sub_decimal"),
+            None => overflow_violation("sub_decimal", SATURATED_FLOOR),
         };
         if is_valid_positive_value(result) {
             Positive(result)
         } else {
-            invariant_panic("sub_decimal")
+            Positive(invariant_broken("sub_decimal", SATURATED_FLOOR))
         }
     }
 }
@@ -1412,12 +1774,12 @@ impl Sub<&Decimal> for Positive {
     fn sub(self, rhs: &Decimal) -> Self::Output {
         let result = match self.0.checked_sub(*rhs) {
             Some(v) => v,
-            None => overflow_panic("sub_decimal"),
+            None => overflow_violation("sub_decimal", SATURATED_FLOOR),
         };
         if is_valid_positive_value(result) {
             Positive(result)
         } else {
-            invariant_panic("sub_decimal")
+            Positive(invariant_broken("sub_decimal", SATURATED_FLOOR))
         }
     }
 }
@@ -1426,8 +1788,8 @@ impl AddAssign for Positive {
     #[inline]
     fn add_assign(&mut self, other: Positive) {
         match self.0.checked_add(other.0) {
-            Some(v) => self.0 = v,
-            None => overflow_panic("add_assign"),
+            Some(v) => *self = Positive::from_op(v, "add_assign"),
+            None => self.0 = overflow_violation("add_assign", Decimal::MAX),
         }
     }
 }
@@ -1437,12 +1799,12 @@ impl AddAssign<Decimal> for Positive {
     fn add_assign(&mut self, rhs: Decimal) {
         let result = match self.0.checked_add(rhs) {
             Some(v) => v,
-            None => overflow_panic("add_assign_decimal"),
+            None => overflow_violation("add_assign_decimal", Decimal::MAX),
         };
         if is_valid_positive_value(result) {
             self.0 = result;
         } else {
-            invariant_panic("add_assign_decimal");
+            self.0 = invariant_broken("add_assign_decimal", SATURATED_FLOOR);
         }
     }
 }
@@ -1452,12 +1814,12 @@ impl MulAssign<Decimal> for Positive {
     fn mul_assign(&mut self, rhs: Decimal) {
         let result = match self.0.checked_mul(rhs) {
             Some(v) => v,
-            None => overflow_panic("mul_assign_decimal"),
+            None => overflow_violation("mul_assign_decimal", positive_saturation(rhs)),
         };
         if is_valid_positive_value(result) {
             self.0 = result;
         } else {
-            invariant_panic("mul_assign_decimal");
+            self.0 = invariant_broken("mul_assign_decimal", SATURATED_FLOOR);
         }
     }
 }
@@ -1469,16 +1831,16 @@ impl Div<Decimal> for Positive {
     #[inline]
     fn div(self, rhs: Decimal) -> Positive {
         if rhs.is_zero() {
-            invariant_panic("div_decimal");
+            return Positive(invariant_broken("div_decimal", Decimal::MAX));
         }
         let result = match self.0.checked_div(rhs) {
             Some(v) => round_div(v),
-            None => overflow_panic("div_decimal"),
+            None => overflow_violation("div_decimal", positive_saturation(rhs)),
         };
         if is_valid_positive_value(result) {
             Positive(result)
         } else {
-            invariant_panic("div_decimal")
+            Positive(invariant_broken("div_decimal", SATURATED_FLOOR))
         }
     }
 }
@@ -1490,16 +1852,16 @@ impl Div<&Decimal> for Positive {
     #[inline]
     fn div(self, rhs: &Decimal) -> Self::Output {
         if rhs.is_zero() {
-            invariant_panic("div_decimal");
+            return Positive(invariant_broken("div_decimal", Decimal::MAX));
         }
         let result = match self.0.checked_div(*rhs) {
             Some(v) => round_div(v),
-            None => overflow_panic("div_decimal"),
+            None => overflow_violation("div_decimal", positive_saturation(*rhs)),
         };
         if is_valid_positive_value(result) {
             Positive(result)
         } else {
-            invariant_panic("div_decimal")
+            Positive(invariant_broken("div_decimal", SATURATED_FLOOR))
         }
     }
 }
@@ -1516,8 +1878,8 @@ impl Mul for Positive {
     #[inline]
     fn mul(self, other: Positive) -> Positive {
         match self.0.checked_mul(other.0) {
-            Some(v) => Positive(v),
-            None => overflow_panic("mul"),
+            Some(v) => Positive::from_op(v, "mul"),
+            None => Positive(overflow_violation("mul", Decimal::MAX)),
         }
     }
 }
@@ -1528,12 +1890,12 @@ impl Mul<Decimal> for Positive {
     fn mul(self, rhs: Decimal) -> Positive {
         let result = match self.0.checked_mul(rhs) {
             Some(v) => v,
-            None => overflow_panic("mul_decimal"),
+            None => overflow_violation("mul_decimal", positive_saturation(rhs)),
         };
         if is_valid_positive_value(result) {
             Positive(result)
         } else {
-            invariant_panic("mul_decimal")
+            Positive(invariant_broken("mul_decimal", SATURATED_FLOOR))
         }
     }
 }
@@ -1544,7 +1906,7 @@ impl Mul<Positive> for Decimal {
     fn mul(self, rhs: Positive) -> Decimal {
         match self.checked_mul(rhs.0) {
             Some(v) => v,
-            None => overflow_panic("mul_decimal_by_positive"),
+            None => overflow_violation("mul_decimal_by_positive", decimal_saturation(self)),
         }
     }
 }
@@ -1554,11 +1916,11 @@ impl Div<Positive> for Decimal {
     #[inline]
     fn div(self, rhs: Positive) -> Decimal {
         if rhs.0.is_zero() {
-            invariant_panic("div_decimal_by_positive");
+            return invariant_broken("div_decimal_by_positive", decimal_saturation(self));
         }
         match self.checked_div(rhs.0) {
             Some(v) => v,
-            None => overflow_panic("div_decimal_by_positive"),
+            None => overflow_violation("div_decimal_by_positive", decimal_saturation(self)),
         }
     }
 }
@@ -1569,7 +1931,7 @@ impl Sub<Positive> for Decimal {
     fn sub(self, rhs: Positive) -> Decimal {
         match self.checked_sub(rhs.0) {
             Some(v) => v,
-            None => overflow_panic("sub_decimal_by_positive"),
+            None => overflow_violation("sub_decimal_by_positive", Decimal::MIN),
         }
     }
 }
@@ -1580,7 +1942,7 @@ impl Sub<&Positive> for Decimal {
     fn sub(self, rhs: &Positive) -> Decimal {
         match self.checked_sub(rhs.0) {
             Some(v) => v,
-            None => overflow_panic("sub_decimal_by_positive"),
+            None => overflow_violation("sub_decimal_by_positive", Decimal::MIN),
         }
     }
 }
@@ -1591,7 +1953,7 @@ impl Add<Positive> for Decimal {
     fn add(self, rhs: Positive) -> Decimal {
         match self.checked_add(rhs.0) {
             Some(v) => v,
-            None => overflow_panic("add_decimal_by_positive"),
+            None => overflow_violation("add_decimal_by_positive", Decimal::MAX),
         }
     }
 }
@@ -1602,47 +1964,53 @@ impl Add<&Positive> for Decimal {
     fn add(self, rhs: &Positive) -> Decimal {
         match self.checked_add(rhs.0) {
             Some(v) => v,
-            None => overflow_panic("add_decimal_by_positive"),
+            None => overflow_violation("add_decimal_by_positive", Decimal::MAX),
         }
     }
 }
 
-impl std::ops::AddAssign<Positive> for Decimal {
+impl core::ops::AddAssign<Positive> for Decimal {
     #[inline]
     fn add_assign(&mut self, rhs: Positive) {
         match self.checked_add(rhs.0) {
             Some(v) => *self = v,
-            None => overflow_panic("add_assign_decimal_by_positive"),
+            None => *self = overflow_violation("add_assign_decimal_by_positive", Decimal::MAX),
         }
     }
 }
 
-impl std::ops::AddAssign<&Positive> for Decimal {
+impl core::ops::AddAssign<&Positive> for Decimal {
     #[inline]
     fn add_assign(&mut self, rhs: &Positive) {
         match self.checked_add(rhs.0) {
             Some(v) => *self = v,
-            None => overflow_panic("add_assign_decimal_by_positive"),
+            None => *self = overflow_violation("add_assign_decimal_by_positive", Decimal::MAX),
         }
     }
 }
 
-impl std::ops::MulAssign<Positive> for Decimal {
+impl core::ops::MulAssign<Positive> for Decimal {
     #[inline]
     fn mul_assign(&mut self, rhs: Positive) {
         match self.checked_mul(rhs.0) {
             Some(v) => *self = v,
-            None => overflow_panic("mul_assign_decimal_by_positive"),
+            None => {
+                *self =
+                    overflow_violation("mul_assign_decimal_by_positive", decimal_saturation(*self))
+            }
         }
     }
 }
 
-impl std::ops::MulAssign<&Positive> for Decimal {
+impl core::ops::MulAssign<&Positive> for Decimal {
     #[inline]
     fn mul_assign(&mut self, rhs: &Positive) {
         match self.checked_mul(rhs.0) {
             Some(v) => *self = v,
-            None => overflow_panic("mul_assign_decimal_by_positive"),
+            None => {
+                *self =
+                    overflow_violation("mul_assign_decimal_by_positive", decimal_saturation(*self))
+            }
         }
     }
 }
@@ -1654,6 +2022,13 @@ impl PartialEq<Positive> for Decimal {
     }
 }
 
+impl PartialOrd<Positive> for Decimal {
+    #[inline]
+    fn partial_cmp(&self, other: &Positive) -> Option<Ordering> {
+        self.partial_cmp(&other.0)
+    }
+}
+
 impl From<&Positive> for Decimal {
     #[inline]
     fn from(pos: &Positive) -> Self {
@@ -1675,6 +2050,7 @@ impl Default for Positive {
     }
 }
 
+#[cfg(feature = "approx")]
 impl AbsDiffEq for Positive {
     type Epsilon = Decimal;
 
@@ -1687,6 +2063,7 @@ impl AbsDiffEq for Positive {
     }
 }
 
+#[cfg(feature = "approx")]
 impl RelativeEq for Positive {
     fn default_max_relative() -> Self::Epsilon {
         EPSILON_CMP
@@ -1704,6 +2081,22 @@ impl RelativeEq for Positive {
     }
 }
 
+#[cfg(feature = "approx")]
+impl UlpsEq for Positive {
+    /// Four units in the last place, matching `approx`'s default for
+    /// floating-point types.
+    fn default_max_ulps() -> u32 {
+        4
+    }
+
+    /// Returns `true` when the values are within `epsilon`, or within
+    /// `max_ulps` units in the last place of the larger of their two
+    /// `Decimal` scales (see [`Tolerance::Ulps`]).
+    fn ulps_eq(&self, other: &Self, epsilon: Self::Epsilon, max_ulps: u32) -> bool {
+        self.abs_diff_eq(other, epsilon) || Tolerance::Ulps(max_ulps).is_close(self.0, other.0)
+    }
+}
+
 #[cfg(not(feature = "non-zero"))]
 impl Sum for Positive {
     fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
@@ -15,11 +15,13 @@
 //!
 //! This includes:
 //! - The `Positive` type and its associated macros
+//! - The `PositiveRange` grid iterator
+//! - The `PositiveExpr` fallible expression builder
 //! - Error types for handling failures
 //! - The `Decimal` type from `rust_decimal`
 //! - All predefined constants
 
 pub use crate::constants::*;
-pub use crate::error::{PositiveError, PositiveResult};
-pub use crate::{Positive, is_positive, pos, pos_or_panic, spos};
+pub use crate::error::{Operation, PositiveError, PositiveResult};
+pub use crate::{Positive, PositiveExpr, PositiveRange, is_positive, pos, pos_or_panic, spos};
 pub use rust_decimal::Decimal;
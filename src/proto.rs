@@ -0,0 +1,151 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 18/10/26
+******************************************************************************/
+
+//! Protobuf representation of `Positive` through `prost`.
+//!
+//! [`v1::Positive`] is the message defined in
+//! `proto/positive/v1/positive.proto`: the unsigned mantissa as big-endian
+//! bytes and the scale, so every `Positive` converts exactly, without the
+//! precision loss of a `double`. The generated code is checked in, so
+//! building does not need `protoc`; other languages can generate their
+//! types from the same file.
+//!
+//! `From<Positive>` always succeeds and produces the canonical form, with
+//! no leading zero bytes in the mantissa. `TryFrom<v1::Positive>` accepts
+//! leading zeros but rejects a mantissa wider than 96 bits, a scale above
+//! 28 and, under the `non-zero` feature, zero.
+//!
+//! ```rust
+//! use positive::{Positive, pos_or_panic, proto};
+//! use prost::Message;
+//!
+//! let message = proto::v1::Positive::from(pos_or_panic!(101.25));
+//! assert_eq!(message.mantissa, [0x27, 0x8D]);
+//! assert_eq!(message.scale, 2);
+//!
+//! let bytes = message.encode_to_vec();
+//! let decoded = proto::v1::Positive::decode(bytes.as_slice()).unwrap();
+//! assert_eq!(Positive::try_from(decoded).unwrap(), pos_or_panic!(101.25));
+//! ```
+
+use crate::binary::from_parts;
+use crate::error::PositiveError;
+use crate::positive::Positive;
+
+/// Messages of the `positive.v1` protobuf package.
+pub mod v1 {
+    include!("proto/positive.v1.rs");
+}
+
+impl From<Positive> for v1::Positive {
+    fn from(value: Positive) -> Self {
+        let decimal = value.to_dec();
+        let bytes = decimal.mantissa().unsigned_abs().to_be_bytes();
+        let start = bytes
+            .iter()
+            .position(|&byte| byte != 0)
+            .unwrap_or(bytes.len());
+        v1::Positive {
+            mantissa: bytes[start..].to_vec(),
+            scale: decimal.scale(),
+        }
+    }
+}
+
+impl TryFrom<v1::Positive> for Positive {
+    type Error = PositiveError;
+
+    fn try_from(value: v1::Positive) -> Result<Self, Self::Error> {
+        let start = value
+            .mantissa
+            .iter()
+            .position(|&byte| byte != 0)
+            .unwrap_or(value.mantissa.len());
+        let digits = &value.mantissa[start..];
+        if digits.len() > 16 {
+            return Err(PositiveError::conversion_error(
+                "bytes",
+                "Decimal",
+                "mantissa exceeds 96 bits",
+            ));
+        }
+        let mantissa = digits
+            .iter()
+            .fold(0u128, |acc, &byte| acc << 8 | u128::from(byte));
+        from_parts(mantissa, false, value.scale)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+    use prost::{Message, Name};
+    use rust_decimal::Decimal;
+    use rust_decimal_macros::dec;
+
+    fn message(mantissa: &[u8], scale: u32) -> v1::Positive {
+        v1::Positive {
+            mantissa: mantissa.to_vec(),
+            scale,
+        }
+    }
+
+    #[test]
+    fn test_wire_format() {
+        let value = Positive::new_decimal(dec!(101.25)).unwrap();
+        let bytes = v1::Positive::from(value).encode_to_vec();
+        assert_eq!(bytes, [0x0A, 0x02, 0x27, 0x8D, 0x10, 0x02]);
+        assert_eq!(v1::Positive::full_name(), "positive.v1.Positive");
+    }
+
+    #[test]
+    fn test_round_trip_is_exact() {
+        for value in [
+            Positive::ONE,
+            Positive::new_decimal(dec!(0.0000000000000000000000000001)).unwrap(),
+            Positive::new_decimal(dec!(123.4500)).unwrap(),
+            Positive::INFINITY,
+        ] {
+            let bytes = v1::Positive::from(value).encode_to_vec();
+            let back = Positive::try_from(v1::Positive::decode(bytes.as_slice()).unwrap()).unwrap();
+            assert_eq!(back.to_dec(), value.to_dec());
+            assert_eq!(back.to_dec().scale(), value.to_dec().scale());
+        }
+        assert_eq!(
+            v1::Positive::from(Positive::INFINITY).mantissa,
+            vec![0xFF; 12]
+        );
+    }
+
+    #[test]
+    fn test_leading_zeros_are_accepted() {
+        let value = Positive::try_from(message(&[0, 0, 0x27, 0x8D], 2)).unwrap();
+        assert_eq!(value.to_dec(), dec!(101.25));
+    }
+
+    #[test]
+    fn test_invalid_messages_are_rejected() {
+        let err = Positive::try_from(message(&[1; 13], 0)).unwrap_err();
+        assert_eq!(err.code(), "conversion");
+        let err = Positive::try_from(message(&[1; 17], 0)).unwrap_err();
+        assert_eq!(err.code(), "conversion");
+        let err = Positive::try_from(message(&[1], 29)).unwrap_err();
+        assert_eq!(err.code(), "invalid_precision");
+    }
+
+    #[test]
+    fn test_zero() {
+        let zero = Positive::try_from(v1::Positive::default());
+        if cfg!(feature = "non-zero") {
+            assert!(zero.is_err());
+        } else {
+            let zero = zero.unwrap();
+            assert_eq!(zero.to_dec(), Decimal::ZERO);
+            assert!(v1::Positive::from(zero).mantissa.is_empty());
+        }
+    }
+}
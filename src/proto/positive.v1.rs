@@ -0,0 +1,25 @@
+// This file is @generated by prost-build.
+/// An exact non-negative decimal number: `mantissa * 10^-scale`.
+///
+/// Mirrors `positive::Positive`, which wraps a `rust_decimal::Decimal`, so
+/// every value round-trips without loss.
+#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct Positive {
+    /// The unsigned mantissa as big-endian bytes without leading zeros.
+    /// Empty means zero. At most 96 bits.
+    #[prost(bytes = "vec", tag = "1")]
+    pub mantissa: ::prost::alloc::vec::Vec<u8>,
+    /// The number of decimal places, at most 28.
+    #[prost(uint32, tag = "2")]
+    pub scale: u32,
+}
+impl ::prost::Name for Positive {
+    const NAME: &'static str = "Positive";
+    const PACKAGE: &'static str = "positive.v1";
+    fn full_name() -> ::prost::alloc::string::String {
+        "positive.v1.Positive".into()
+    }
+    fn type_url() -> ::prost::alloc::string::String {
+        "/positive.v1.Positive".into()
+    }
+}
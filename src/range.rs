@@ -0,0 +1,679 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 18/10/26
+******************************************************************************/
+
+//! Grid iterators over `Positive` values.
+//!
+//! This module provides [`PositiveRange`], the iterator returned by
+//! [`Positive::range`], [`Positive::range_inclusive`], [`Positive::linspace`],
+//! [`Positive::logspace`], [`Positive::geomspace`] and [`Positive::around`].
+//! It is intended for strike ladders, price grids and similar sequences.
+//!
+//! Every element is computed directly from its index (`start + step * i`,
+//! `start + (end - start) * i / (n - 1)`, ...) instead of by repeated
+//! addition, so no rounding error accumulates along the grid. Endpoints of
+//! `linspace`, `logspace` and `geomspace` are returned exactly.
+//!
+//! ```rust
+//! use positive::{Positive, pos_or_panic};
+//!
+//! let strikes: Vec<Positive> =
+//!     Positive::around(pos_or_panic!(100.0), pos_or_panic!(2.5), 2)
+//!         .unwrap()
+//!         .collect();
+//! assert_eq!(strikes.len(), 5);
+//! assert_eq!(strikes[0], 95.0);
+//! assert_eq!(strikes[4], 105.0);
+//! ```
+
+use crate::error::{Operation, PositiveError, PositiveResult};
+use crate::positive::{Positive, is_valid_positive_value};
+use core::iter::FusedIterator;
+use core::ops::{Bound, RangeBounds};
+use num_traits::ToPrimitive;
+use rust_decimal::{Decimal, MathematicalOps};
+
+/// How the value at a given index of a [`PositiveRange`] is computed.
+#[derive(Clone, Debug)]
+enum Spacing {
+    /// `start + step * i`.
+    Arithmetic { start: Decimal, step: Decimal },
+    /// `start + (end - start) * i / intervals`.
+    Linear {
+        start: Decimal,
+        end: Decimal,
+        intervals: Decimal,
+    },
+    /// `base ^ (start_exp + (end_exp - start_exp) * i / intervals)`.
+    Logarithmic {
+        base: Decimal,
+        start_exp: Decimal,
+        end_exp: Decimal,
+        intervals: Decimal,
+    },
+    /// `start * (end / start) ^ (i / intervals)`.
+    Geometric {
+        start: Decimal,
+        end: Decimal,
+        ratio: Decimal,
+        intervals: Decimal,
+    },
+}
+
+/// An iterator over a finite grid of `Positive` values.
+///
+/// Implements [`Iterator`], [`DoubleEndedIterator`] (use `.rev()` for a
+/// descending ladder) and [`ExactSizeIterator`]. It also implements
+/// [`RangeBounds<Positive>`], so [`RangeBounds::contains`] reports whether
+/// a value lies within the span covered by the grid.
+///
+/// # Examples
+///
+/// ```rust
+/// use positive::{Positive, pos_or_panic};
+/// use std::ops::RangeBounds;
+///
+/// let grid = Positive::range(pos_or_panic!(90.0), pos_or_panic!(110.0), pos_or_panic!(5.0))
+///     .unwrap();
+/// assert_eq!(grid.len(), 4);
+/// assert!(grid.contains(&pos_or_panic!(109.0)));
+/// assert!(!grid.contains(&pos_or_panic!(110.0)));
+///
+/// let descending: Vec<Positive> = grid.rev().collect();
+/// assert_eq!(descending[0], 105.0);
+/// ```
+#[derive(Clone, Debug)]
+pub struct PositiveRange {
+    spacing: Spacing,
+    front: usize,
+    back: usize,
+    lower: Positive,
+    upper: Bound<Positive>,
+}
+
+impl PositiveRange {
+    /// Returns the value at absolute index `index` of the grid.
+    ///
+    /// Only called with `index < self.back`, where the value is known to lie
+    /// between the validated endpoints of the grid.
+    fn value_at(&self, index: usize) -> Positive {
+        let i = Decimal::from(index);
+        let value = match self.spacing {
+            Spacing::Arithmetic { start, step } => start + step * i,
+            Spacing::Linear {
+                start,
+                end,
+                intervals,
+            } => {
+                if index == 0 {
+                    start
+                } else if i == intervals {
+                    end
+                } else {
+                    interpolate(start, end, i, intervals)
+                }
+            }
+            Spacing::Logarithmic {
+                base,
+                start_exp,
+                end_exp,
+                intervals,
+            } => {
+                let exponent = if index == 0 {
+                    start_exp
+                } else if i == intervals {
+                    end_exp
+                } else {
+                    interpolate(start_exp, end_exp, i, intervals)
+                };
+                base.powd(exponent)
+            }
+            Spacing::Geometric {
+                start,
+                end,
+                ratio,
+                intervals,
+            } => {
+                if index == 0 {
+                    start
+                } else if i == intervals {
+                    end
+                } else {
+                    start * ratio.powd(i / intervals)
+                }
+            }
+        };
+        // SAFETY: every spacing yields values between two validated
+        // `Positive` endpoints (or a validated `base` raised to a power,
+        // which is strictly positive), so the invariant holds.
+        unsafe { Positive::new_unchecked(value) }
+    }
+}
+
+/// Computes `start + (end - start) * i / intervals` without accumulating
+/// error, falling back to dividing first when the product would overflow.
+#[inline]
+fn interpolate(start: Decimal, end: Decimal, i: Decimal, intervals: Decimal) -> Decimal {
+    let diff = end - start;
+    let offset = match diff.checked_mul(i) {
+        Some(scaled) => scaled / intervals,
+        None => diff / intervals * i,
+    };
+    start + offset
+}
+
+/// Converts a grid length computed as a `Decimal` into a `usize`.
+fn grid_len(count: Decimal) -> PositiveResult<usize> {
+    count.to_usize().ok_or_else(too_many_elements)
+}
+
+fn too_many_elements() -> PositiveError {
+    PositiveError::conversion_error("Decimal", "usize", "range has too many elements")
+}
+
+impl Iterator for PositiveRange {
+    type Item = Positive;
+
+    #[inline]
+    fn next(&mut self) -> Option<Positive> {
+        if self.front < self.back {
+            let value = self.value_at(self.front);
+            self.front += 1;
+            Some(value)
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.back - self.front;
+        (remaining, Some(remaining))
+    }
+
+    #[inline]
+    fn nth(&mut self, n: usize) -> Option<Positive> {
+        self.front = self.front.saturating_add(n).min(self.back);
+        self.next()
+    }
+
+    #[inline]
+    fn last(mut self) -> Option<Positive> {
+        self.next_back()
+    }
+}
+
+impl DoubleEndedIterator for PositiveRange {
+    #[inline]
+    fn next_back(&mut self) -> Option<Positive> {
+        if self.front < self.back {
+            self.back -= 1;
+            Some(self.value_at(self.back))
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    fn nth_back(&mut self, n: usize) -> Option<Positive> {
+        self.back = self.back.saturating_sub(n).max(self.front);
+        self.next_back()
+    }
+}
+
+impl ExactSizeIterator for PositiveRange {}
+
+impl FusedIterator for PositiveRange {}
+
+impl RangeBounds<Positive> for PositiveRange {
+    fn start_bound(&self) -> Bound<&Positive> {
+        Bound::Included(&self.lower)
+    }
+
+    fn end_bound(&self) -> Bound<&Positive> {
+        self.upper.as_ref()
+    }
+}
+
+impl Positive {
+    /// Returns the grid `start, start + step, ...` up to but excluding `end`.
+    ///
+    /// Returns an empty range when `end <= start`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `ArithmeticError` if `step` is zero, or a
+    /// `ConversionError` if the number of elements does not fit in `usize`.
+    pub fn range(start: Positive, end: Positive, step: Positive) -> PositiveResult<PositiveRange> {
+        Self::arithmetic_range(start, end, step, false)
+    }
+
+    /// Returns the grid `start, start + step, ...` up to and including `end`.
+    ///
+    /// The last element is the largest grid point `<= end`; it equals `end`
+    /// only when `end - start` is a multiple of `step`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `ArithmeticError` if `step` is zero, or a
+    /// `ConversionError` if the number of elements does not fit in `usize`.
+    pub fn range_inclusive(
+        start: Positive,
+        end: Positive,
+        step: Positive,
+    ) -> PositiveResult<PositiveRange> {
+        Self::arithmetic_range(start, end, step, true)
+    }
+
+    fn arithmetic_range(
+        start: Positive,
+        end: Positive,
+        step: Positive,
+        inclusive: bool,
+    ) -> PositiveResult<PositiveRange> {
+        if step.is_zero() {
+            return Err(PositiveError::arithmetic_error(
+                Operation::Range,
+                "step must be greater than zero",
+            ));
+        }
+        let (start_dec, end_dec, step_dec) = (start.to_dec(), end.to_dec(), step.to_dec());
+        let len = if end_dec < start_dec || (!inclusive && end_dec == start_dec) {
+            0
+        } else {
+            let steps = (end_dec - start_dec)
+                .checked_div(step_dec)
+                .ok_or_else(too_many_elements)?;
+            let mut len = if inclusive {
+                grid_len(steps.floor())? + 1
+            } else {
+                grid_len(steps.ceil())?
+            };
+            // Guard against the quotient rounding onto the wrong side of
+            // an integer at the edge of `Decimal` precision.
+            while len > 0 {
+                let last = start_dec + step_dec * Decimal::from(len - 1);
+                if last < end_dec || (inclusive && last == end_dec) {
+                    break;
+                }
+                len -= 1;
+            }
+            len
+        };
+        Ok(PositiveRange {
+            spacing: Spacing::Arithmetic {
+                start: start_dec,
+                step: step_dec,
+            },
+            front: 0,
+            back: len,
+            lower: start,
+            upper: if inclusive {
+                Bound::Included(end)
+            } else {
+                Bound::Excluded(end)
+            },
+        })
+    }
+
+    /// Returns `n` evenly spaced values from `start` to `end`, both included.
+    ///
+    /// `start` may be greater than `end`, producing a descending grid. With
+    /// `n == 1` only `start` is returned; with `n == 0` the grid is empty.
+    #[must_use]
+    pub fn linspace(start: Positive, end: Positive, n: usize) -> PositiveRange {
+        PositiveRange {
+            spacing: Spacing::Linear {
+                start: start.to_dec(),
+                end: end.to_dec(),
+                intervals: Decimal::from(n.saturating_sub(1)),
+            },
+            front: 0,
+            back: n,
+            lower: start.min(end),
+            upper: Bound::Included(start.max(end)),
+        }
+    }
+
+    /// Returns `n` values `base ^ e` where the exponents `e` are evenly
+    /// spaced from `start_exp` to `end_exp`, both included.
+    ///
+    /// Integer exponents are computed exactly, so
+    /// `logspace(0, 3, 4, TEN)` yields exactly `1, 10, 100, 1000`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `ArithmeticError` if `base` is zero or if either endpoint
+    /// overflows `Decimal`.
+    pub fn logspace(
+        start_exp: Decimal,
+        end_exp: Decimal,
+        n: usize,
+        base: Positive,
+    ) -> PositiveResult<PositiveRange> {
+        if base.is_zero() {
+            return Err(PositiveError::arithmetic_error(
+                Operation::Logspace,
+                "base must be greater than zero",
+            ));
+        }
+        let endpoint = |exp: Decimal| {
+            base.to_dec()
+                .checked_powd(exp)
+                .filter(|value| is_valid_positive_value(*value))
+                .ok_or_else(|| {
+                    PositiveError::arithmetic_error(Operation::Logspace, "endpoint overflow")
+                })
+        };
+        let (first, last) = (endpoint(start_exp)?, endpoint(end_exp)?);
+        // SAFETY: both endpoints were validated by `is_valid_positive_value`.
+        let (first, last) = unsafe {
+            (
+                Positive::new_unchecked(first),
+                Positive::new_unchecked(last),
+            )
+        };
+        Ok(PositiveRange {
+            spacing: Spacing::Logarithmic {
+                base: base.to_dec(),
+                start_exp,
+                end_exp,
+                intervals: Decimal::from(n.saturating_sub(1)),
+            },
+            front: 0,
+            back: n,
+            lower: first.min(last),
+            upper: Bound::Included(first.max(last)),
+        })
+    }
+
+    /// Returns `n` values from `start` to `end`, both included, with a
+    /// constant ratio between consecutive elements.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `ArithmeticError` if `start` or `end` is zero, or if the
+    /// ratio `end / start` overflows `Decimal`.
+    pub fn geomspace(start: Positive, end: Positive, n: usize) -> PositiveResult<PositiveRange> {
+        if start.is_zero() || end.is_zero() {
+            return Err(PositiveError::arithmetic_error(
+                Operation::Geomspace,
+                "endpoints must be greater than zero",
+            ));
+        }
+        let ratio = end.to_dec().checked_div(start.to_dec()).ok_or_else(|| {
+            PositiveError::arithmetic_error(Operation::Geomspace, "endpoint ratio overflows")
+        })?;
+        Ok(PositiveRange {
+            spacing: Spacing::Geometric {
+                start: start.to_dec(),
+                end: end.to_dec(),
+                ratio,
+                intervals: Decimal::from(n.saturating_sub(1)),
+            },
+            front: 0,
+            back: n,
+            lower: start.min(end),
+            upper: Bound::Included(start.max(end)),
+        })
+    }
+
+    /// Returns the `2 * n_each_side + 1` values
+    /// `spot - n_each_side * step, ..., spot, ..., spot + n_each_side * step`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `ArithmeticError` if `step` is zero or the upper end
+    /// overflows, or an `OutOfBounds` error if the lowest grid point would
+    /// violate the positivity invariant.
+    pub fn around(
+        spot: Positive,
+        step: Positive,
+        n_each_side: usize,
+    ) -> PositiveResult<PositiveRange> {
+        if step.is_zero() {
+            return Err(PositiveError::arithmetic_error(
+                Operation::Around,
+                "step must be greater than zero",
+            ));
+        }
+        let width = step
+            .to_dec()
+            .checked_mul(Decimal::from(n_each_side))
+            .ok_or_else(|| PositiveError::arithmetic_error(Operation::Around, "overflow"))?;
+        let lower = Positive::new_decimal(spot.to_dec() - width)?;
+        let upper = spot
+            .to_dec()
+            .checked_add(width)
+            .ok_or_else(|| PositiveError::arithmetic_error(Operation::Around, "overflow"))?;
+        let len = n_each_side
+            .checked_mul(2)
+            .and_then(|len| len.checked_add(1))
+            .ok_or_else(|| {
+                PositiveError::conversion_error("Decimal", "usize", "range has too many elements")
+            })?;
+        // SAFETY: `upper >= spot`, and `spot` is a valid `Positive`.
+        let upper = unsafe { Positive::new_unchecked(upper) };
+        Ok(PositiveRange {
+            spacing: Spacing::Arithmetic {
+                start: lower.to_dec(),
+                step: step.to_dec(),
+            },
+            front: 0,
+            back: len,
+            lower,
+            upper: Bound::Included(upper),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+    use alloc::vec::Vec;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_range_exclusive() {
+        let values: Vec<Positive> =
+            Positive::range(pos_or_panic!(1.0), pos_or_panic!(2.0), pos_or_panic!(0.25))
+                .unwrap()
+                .collect();
+        assert_eq!(
+            values,
+            vec![
+                pos_or_panic!(1.0),
+                pos_or_panic!(1.25),
+                pos_or_panic!(1.5),
+                pos_or_panic!(1.75)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_range_inclusive() {
+        let values: Vec<Positive> =
+            Positive::range_inclusive(pos_or_panic!(1.0), pos_or_panic!(2.0), pos_or_panic!(0.25))
+                .unwrap()
+                .collect();
+        assert_eq!(values.len(), 5);
+        assert_eq!(values[4], pos_or_panic!(2.0));
+
+        let mut partial =
+            Positive::range_inclusive(pos_or_panic!(1.0), pos_or_panic!(2.0), pos_or_panic!(0.3))
+                .unwrap();
+        assert_eq!(partial.next_back(), Some(pos_or_panic!(1.9)));
+    }
+
+    #[test]
+    fn test_range_empty_and_zero_step() {
+        assert_eq!(
+            Positive::range(pos_or_panic!(5.0), pos_or_panic!(5.0), pos_or_panic!(1.0))
+                .unwrap()
+                .len(),
+            0
+        );
+        assert_eq!(
+            Positive::range(pos_or_panic!(5.0), pos_or_panic!(1.0), pos_or_panic!(1.0))
+                .unwrap()
+                .len(),
+            0
+        );
+        #[cfg(not(feature = "non-zero"))]
+        assert!(Positive::range(pos_or_panic!(1.0), pos_or_panic!(5.0), Positive::ZERO).is_err());
+    }
+
+    #[test]
+    fn test_range_no_accumulated_error() {
+        let mut grid = Positive::range(
+            pos_or_panic!(0.1),
+            pos_or_panic!(1000.0),
+            pos_or_panic!(0.1),
+        )
+        .unwrap();
+        assert_eq!(grid.len(), 9999);
+        assert_eq!(grid.clone().nth(9998), Some(pos_or_panic!(999.9)));
+        assert_eq!(grid.next_back(), Some(pos_or_panic!(999.9)));
+    }
+
+    #[test]
+    fn test_double_ended_and_exact_size() {
+        let mut grid =
+            Positive::range(pos_or_panic!(1.0), pos_or_panic!(6.0), pos_or_panic!(1.0)).unwrap();
+        assert_eq!(grid.len(), 5);
+        assert_eq!(grid.next(), Some(pos_or_panic!(1.0)));
+        assert_eq!(grid.next_back(), Some(pos_or_panic!(5.0)));
+        assert_eq!(grid.len(), 3);
+        assert_eq!(grid.nth_back(1), Some(pos_or_panic!(3.0)));
+        assert_eq!(grid.next(), Some(pos_or_panic!(2.0)));
+        assert_eq!(grid.next(), None);
+        assert_eq!(grid.next_back(), None);
+    }
+
+    #[test]
+    fn test_linspace() {
+        let values: Vec<Positive> =
+            Positive::linspace(pos_or_panic!(1.0), pos_or_panic!(2.0), 5).collect();
+        assert_eq!(
+            values,
+            vec![
+                pos_or_panic!(1.0),
+                pos_or_panic!(1.25),
+                pos_or_panic!(1.5),
+                pos_or_panic!(1.75),
+                pos_or_panic!(2.0)
+            ]
+        );
+
+        let thirds: Vec<Positive> =
+            Positive::linspace(pos_or_panic!(0.1), pos_or_panic!(1.1), 4).collect();
+        assert_eq!(thirds[0], pos_or_panic!(0.1));
+        assert_eq!(thirds[3], pos_or_panic!(1.1));
+
+        let descending: Vec<Positive> =
+            Positive::linspace(pos_or_panic!(3.0), pos_or_panic!(1.0), 3).collect();
+        assert_eq!(
+            descending,
+            vec![pos_or_panic!(3.0), pos_or_panic!(2.0), pos_or_panic!(1.0)]
+        );
+
+        assert_eq!(
+            Positive::linspace(pos_or_panic!(1.0), pos_or_panic!(2.0), 0).len(),
+            0
+        );
+        let single: Vec<Positive> =
+            Positive::linspace(pos_or_panic!(1.0), pos_or_panic!(2.0), 1).collect();
+        assert_eq!(single, vec![pos_or_panic!(1.0)]);
+    }
+
+    #[test]
+    fn test_logspace() {
+        let values: Vec<Positive> = Positive::logspace(dec!(-1), dec!(2), 4, Positive::TEN)
+            .unwrap()
+            .collect();
+        assert_eq!(
+            values,
+            vec![
+                pos_or_panic!(0.1),
+                pos_or_panic!(1.0),
+                pos_or_panic!(10.0),
+                pos_or_panic!(100.0)
+            ]
+        );
+        #[cfg(not(feature = "non-zero"))]
+        assert!(Positive::logspace(dec!(0), dec!(1), 3, Positive::ZERO).is_err());
+        assert!(Positive::logspace(dec!(0), dec!(100), 3, Positive::TEN).is_err());
+    }
+
+    #[test]
+    fn test_geomspace() {
+        let values: Vec<Positive> =
+            Positive::geomspace(pos_or_panic!(1.0), pos_or_panic!(1000.0), 4)
+                .unwrap()
+                .collect();
+        assert_eq!(values[0], pos_or_panic!(1.0));
+        assert_eq!(values[3], pos_or_panic!(1000.0));
+        assert!((values[1].to_dec() - dec!(10)).abs() < dec!(1e-10));
+        assert!((values[2].to_dec() - dec!(100)).abs() < dec!(1e-10));
+        #[cfg(not(feature = "non-zero"))]
+        assert!(Positive::geomspace(Positive::ZERO, pos_or_panic!(10.0), 3).is_err());
+    }
+
+    #[test]
+    fn test_grid_size_overflow() {
+        let tiny = Positive::new_decimal(dec!(0.00000000000000000001)).unwrap();
+        let huge = Positive::new_decimal(dec!(100000000000000000000)).unwrap();
+        let err = Positive::range(tiny, huge, tiny).unwrap_err();
+        assert_eq!(err.code(), "conversion");
+        assert!(Positive::range_inclusive(tiny, huge, tiny).is_err());
+
+        let err = Positive::geomspace(
+            Positive::new_decimal(dec!(0.0000000000000000000000000001)).unwrap(),
+            Positive::new_decimal(dec!(10000000000000000000000000000)).unwrap(),
+            3,
+        )
+        .unwrap_err();
+        assert_eq!(err.code(), "arithmetic");
+    }
+
+    #[test]
+    fn test_around() {
+        let values: Vec<Positive> = Positive::around(pos_or_panic!(100.0), pos_or_panic!(2.5), 2)
+            .unwrap()
+            .collect();
+        assert_eq!(
+            values,
+            vec![
+                pos_or_panic!(95.0),
+                pos_or_panic!(97.5),
+                pos_or_panic!(100.0),
+                pos_or_panic!(102.5),
+                pos_or_panic!(105.0)
+            ]
+        );
+        assert!(Positive::around(pos_or_panic!(1.0), pos_or_panic!(1.0), 2).is_err());
+    }
+
+    #[test]
+    fn test_range_bounds_contains() {
+        let grid = Positive::range(
+            pos_or_panic!(90.0),
+            pos_or_panic!(110.0),
+            pos_or_panic!(5.0),
+        )
+        .unwrap();
+        assert!(grid.contains(&pos_or_panic!(90.0)));
+        assert!(grid.contains(&pos_or_panic!(109.99)));
+        assert!(!grid.contains(&pos_or_panic!(110.0)));
+        assert!(!grid.contains(&pos_or_panic!(89.99)));
+
+        let around = Positive::around(pos_or_panic!(100.0), pos_or_panic!(1.0), 3).unwrap();
+        assert!(around.contains(&pos_or_panic!(103.0)));
+        assert!(!around.contains(&pos_or_panic!(103.01)));
+
+        assert!((pos_or_panic!(1.0)..pos_or_panic!(2.0)).contains(&pos_or_panic!(1.5)));
+    }
+}
@@ -0,0 +1,221 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 18/10/26
+******************************************************************************/
+
+//! `rusqlite` support for `Positive`.
+//!
+//! `Positive` implements `ToSql` as `TEXT` holding the exact `Decimal`
+//! string, so values round-trip without loss. To store a `REAL` or an
+//! `INTEGER` column instead, bind the [`Real`] or [`Integer`] wrappers.
+//!
+//! `FromSql` accepts `TEXT`, `REAL` and `INTEGER` values whatever the
+//! storage used, and validates the positivity invariant. Invalid values
+//! fail with `FromSqlError::Other` wrapping the [`PositiveError`]; `NULL`
+//! and `BLOB` values fail with `FromSqlError::InvalidType` (read nullable
+//! columns as `Option<Positive>`).
+//!
+//! ```rust
+//! use positive::sqlite::Real;
+//! use positive::{Positive, pos_or_panic};
+//! use rusqlite::Connection;
+//!
+//! let conn = Connection::open_in_memory().unwrap();
+//! conn.execute_batch("CREATE TABLE fills (exact TEXT, approx REAL)").unwrap();
+//!
+//! let price = pos_or_panic!(101.25);
+//! conn.execute("INSERT INTO fills VALUES (?1, ?2)", (price, Real(price))).unwrap();
+//!
+//! let (exact, approx): (Positive, Positive) = conn
+//!     .query_row("SELECT exact, approx FROM fills", [], |row| Ok((row.get(0)?, row.get(1)?)))
+//!     .unwrap();
+//! assert_eq!(exact, price);
+//! assert_eq!(approx, price);
+//! ```
+
+use crate::error::PositiveError;
+use crate::positive::Positive;
+use alloc::string::ToString;
+use core::str::FromStr;
+use rusqlite::types::{FromSql, FromSqlError, FromSqlResult, ToSql, ToSqlOutput, ValueRef};
+use rust_decimal::Decimal;
+
+/// Binds a `Positive` as an SQLite `REAL`.
+///
+/// Values beyond `f64` precision are rounded. Reads accept any column type,
+/// like `Positive`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Real(pub Positive);
+
+/// Binds a `Positive` as an SQLite `INTEGER`.
+///
+/// Binding fails if the value has a fractional part or does not fit in an
+/// `i64`. Reads accept any column type, like `Positive`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Integer(pub Positive);
+
+impl ToSql for Positive {
+    fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+        Ok(ToSqlOutput::from(self.to_dec().to_string()))
+    }
+}
+
+impl ToSql for Real {
+    fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+        let value = self.0.to_f64_checked().ok_or_else(|| {
+            to_sql_error(PositiveError::conversion_error(
+                "Positive",
+                "f64",
+                "value not representable as f64",
+            ))
+        })?;
+        Ok(ToSqlOutput::from(value))
+    }
+}
+
+impl ToSql for Integer {
+    fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+        let value = self.0.to_dec();
+        if !value.fract().is_zero() {
+            return Err(to_sql_error(PositiveError::conversion_error(
+                "Positive",
+                "i64",
+                "value has a fractional part",
+            )));
+        }
+        let value = i64::try_from(value).map_err(|_| {
+            to_sql_error(PositiveError::conversion_error(
+                "Positive",
+                "i64",
+                "value out of range for i64",
+            ))
+        })?;
+        Ok(ToSqlOutput::from(value))
+    }
+}
+
+impl FromSql for Positive {
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        let result = match value {
+            ValueRef::Text(_) => Positive::from_str(value.as_str()?),
+            ValueRef::Real(real) => Positive::new(real),
+            ValueRef::Integer(integer) => Positive::new_decimal(Decimal::from(integer)),
+            ValueRef::Null | ValueRef::Blob(_) => return Err(FromSqlError::InvalidType),
+        };
+        result.map_err(FromSqlError::other)
+    }
+}
+
+impl FromSql for Real {
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        Positive::column_result(value).map(Real)
+    }
+}
+
+impl FromSql for Integer {
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        Positive::column_result(value).map(Integer)
+    }
+}
+
+fn to_sql_error(error: PositiveError) -> rusqlite::Error {
+    rusqlite::Error::ToSqlConversionFailure(error.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rusqlite::Connection;
+    use rust_decimal_macros::dec;
+
+    fn connection() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch("CREATE TABLE t (v)").unwrap();
+        conn
+    }
+
+    fn read<T: FromSql>(conn: &Connection) -> rusqlite::Result<T> {
+        conn.query_row("SELECT v FROM t", [], |row| row.get(0))
+    }
+
+    #[test]
+    fn test_text_is_exact() {
+        let conn = connection();
+        let value = Positive::new_decimal(dec!(0.1000000000000000000000000001)).unwrap();
+        conn.execute("INSERT INTO t VALUES (?1)", [value]).unwrap();
+        let kind: String = conn
+            .query_row("SELECT typeof(v) FROM t", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(kind, "text");
+        let back: Positive = read(&conn).unwrap();
+        assert_eq!(back.to_dec(), value.to_dec());
+        assert_eq!(back.to_dec().scale(), 28);
+    }
+
+    #[test]
+    fn test_real_and_integer_storage() {
+        let conn = connection();
+        conn.execute(
+            "INSERT INTO t VALUES (?1)",
+            [Real(Positive::new_decimal(dec!(2.5)).unwrap())],
+        )
+        .unwrap();
+        conn.execute("INSERT INTO t VALUES (?1)", [Integer(Positive::TEN)])
+            .unwrap();
+        let mut stmt = conn
+            .prepare("SELECT typeof(v), v FROM t ORDER BY rowid")
+            .unwrap();
+        let rows: Vec<(String, Positive)> = stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(
+            rows[0],
+            (
+                "real".to_string(),
+                Positive::new_decimal(dec!(2.5)).unwrap()
+            )
+        );
+        assert_eq!(rows[1], ("integer".to_string(), Positive::TEN));
+    }
+
+    #[test]
+    fn test_integer_rejects_fraction() {
+        let conn = connection();
+        let err = conn
+            .execute(
+                "INSERT INTO t VALUES (?1)",
+                [Integer(Positive::new_decimal(dec!(1.5)).unwrap())],
+            )
+            .unwrap_err();
+        assert!(matches!(err, rusqlite::Error::ToSqlConversionFailure(_)));
+    }
+
+    #[test]
+    fn test_invalid_values_are_rejected() {
+        for raw in ["-1", "-0.5", "'-2.5'", "'abc'"] {
+            let conn = connection();
+            conn.execute_batch(&format!("INSERT INTO t VALUES ({raw})"))
+                .unwrap();
+            let err = read::<Positive>(&conn).unwrap_err();
+            let rusqlite::Error::FromSqlConversionFailure(_, _, source) = err else {
+                panic!("unexpected error for {raw}: {err:?}");
+            };
+            assert!(source.downcast_ref::<PositiveError>().is_some());
+        }
+    }
+
+    #[test]
+    fn test_null_and_option() {
+        let conn = connection();
+        conn.execute("INSERT INTO t VALUES (?1)", [None::<Positive>])
+            .unwrap();
+        assert_eq!(read::<Option<Positive>>(&conn).unwrap(), None);
+        assert!(matches!(
+            read::<Positive>(&conn),
+            Err(rusqlite::Error::InvalidColumnType(..))
+        ));
+    }
+}
@@ -0,0 +1,177 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 18/10/26
+******************************************************************************/
+
+//! Postgres `NUMERIC` support through `sqlx`.
+//!
+//! `Positive` maps to `NUMERIC` exactly like `Decimal`: encoding and
+//! decoding delegate to the `rust_decimal` support in `sqlx`, and decoding
+//! then validates the positivity invariant. A negative value (or zero
+//! under the `non-zero` feature) fails to decode with a boxed
+//! [`PositiveError`] that can be recovered with `downcast_ref`.
+//! `Option<Positive>` (nullable columns) and `Vec<Positive>`
+//! (`NUMERIC[]`) work through the generic `sqlx` impls.
+//!
+//! ```rust,no_run
+//! use positive::Positive;
+//!
+//! # async fn example(pool: sqlx::PgPool) -> Result<(), sqlx::Error> {
+//! let price: Option<Positive> = sqlx::query_scalar("SELECT price FROM quotes WHERE id = $1")
+//!     .bind(7_i64)
+//!     .fetch_one(&pool)
+//!     .await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::error::PositiveError;
+use crate::positive::Positive;
+use rust_decimal::Decimal;
+use sqlx::encode::IsNull;
+use sqlx::error::BoxDynError;
+use sqlx::postgres::{PgArgumentBuffer, PgHasArrayType, PgTypeInfo, PgValueRef, Postgres};
+use sqlx::{Decode, Encode, Type};
+
+impl Type<Postgres> for Positive {
+    fn type_info() -> PgTypeInfo {
+        <Decimal as Type<Postgres>>::type_info()
+    }
+
+    fn compatible(ty: &PgTypeInfo) -> bool {
+        <Decimal as Type<Postgres>>::compatible(ty)
+    }
+}
+
+impl PgHasArrayType for Positive {
+    fn array_type_info() -> PgTypeInfo {
+        <Decimal as PgHasArrayType>::array_type_info()
+    }
+}
+
+impl Encode<'_, Postgres> for Positive {
+    fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> Result<IsNull, BoxDynError> {
+        <Decimal as Encode<'_, Postgres>>::encode_by_ref(&self.to_dec(), buf)
+    }
+
+    fn size_hint(&self) -> usize {
+        <Decimal as Encode<'_, Postgres>>::size_hint(&self.to_dec())
+    }
+}
+
+impl Decode<'_, Postgres> for Positive {
+    fn decode(value: PgValueRef<'_>) -> Result<Self, BoxDynError> {
+        validate(<Decimal as Decode<'_, Postgres>>::decode(value)?)
+    }
+}
+
+/// Wraps a decoded `NUMERIC`, boxing the `PositiveError` on failure.
+fn validate(value: Decimal) -> Result<Positive, BoxDynError> {
+    Positive::new_decimal(value).map_err(|err: PositiveError| err.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal::MathematicalOps;
+    use rust_decimal_macros::dec;
+
+    fn encode<'q, T: Encode<'q, Postgres>>(value: T) -> (IsNull, Vec<u8>) {
+        let mut buf = PgArgumentBuffer::default();
+        let is_null = value.encode_by_ref(&mut buf).unwrap();
+        (is_null, buf.to_vec())
+    }
+
+    /// Reads the binary `NUMERIC` wire format: digit count, weight, sign
+    /// and display scale, followed by base-10000 digits.
+    fn read_numeric(bytes: &[u8]) -> Decimal {
+        let word = |i: usize| u16::from_be_bytes([bytes[2 * i], bytes[2 * i + 1]]);
+        let (ndigits, weight, sign) = (word(0), word(1) as i16, word(2));
+        let mut value = Decimal::ZERO;
+        for i in 0..ndigits {
+            let exponent = i64::from(weight) - i64::from(i);
+            let base = dec!(10000);
+            let unit = if exponent >= 0 {
+                base.powi(exponent)
+            } else {
+                Decimal::ONE / base.powi(-exponent)
+            };
+            value += Decimal::from(word(4 + usize::from(i))) * unit;
+        }
+        value.set_sign_negative(sign == 0x4000);
+        value
+    }
+
+    #[test]
+    fn test_encodes_like_decimal() {
+        let value = Positive::new_decimal(dec!(12345.6789)).unwrap();
+        assert_eq!(encode(value).1, encode(value.to_dec()).1);
+        assert_eq!(encode(Positive::INFINITY).1, encode(Decimal::MAX).1);
+    }
+
+    #[test]
+    fn test_byte_round_trip() {
+        for value in [
+            pos_or_panic!(1.0),
+            Positive::new_decimal(dec!(0.0001)).unwrap(),
+            Positive::new_decimal(dec!(98765.4321)).unwrap(),
+            Positive::new_decimal(dec!(0.0000000000000000000000000001)).unwrap(),
+            Positive::new_decimal(dec!(100000000000000000000)).unwrap(),
+            Positive::INFINITY,
+        ] {
+            let (is_null, bytes) = encode(value);
+            assert!(matches!(is_null, IsNull::No));
+            let back = validate(read_numeric(&bytes)).unwrap();
+            assert_eq!(back, value);
+        }
+    }
+
+    #[test]
+    fn test_option_encodes_null() {
+        let (is_null, bytes) = encode(None::<Positive>);
+        assert!(matches!(is_null, IsNull::Yes));
+        assert!(bytes.is_empty());
+        assert_eq!(
+            encode(Some(pos_or_panic!(2.5))).1,
+            encode(pos_or_panic!(2.5)).1
+        );
+    }
+
+    #[test]
+    fn test_array_encodes_like_decimal() {
+        let values = vec![pos_or_panic!(1.5), pos_or_panic!(2.25)];
+        let decimals: Vec<Decimal> = values.iter().map(|value| value.to_dec()).collect();
+        assert_eq!(encode(&values).1, encode(&decimals).1);
+    }
+
+    #[test]
+    fn test_negative_decode_is_positive_error() {
+        let (_, bytes) = encode(dec!(-3.5));
+        let err = validate(read_numeric(&bytes)).unwrap_err();
+        let err = err.downcast_ref::<PositiveError>().unwrap();
+        assert_eq!(err.code(), "out_of_bounds");
+    }
+
+    #[test]
+    fn test_zero_decode() {
+        let (_, bytes) = encode(Decimal::ZERO);
+        let decoded = validate(read_numeric(&bytes));
+        #[cfg(feature = "non-zero")]
+        assert!(decoded.unwrap_err().is::<PositiveError>());
+        #[cfg(not(feature = "non-zero"))]
+        assert_eq!(decoded.unwrap(), Positive::ZERO);
+    }
+
+    #[test]
+    fn test_type_info_is_numeric() {
+        assert_eq!(
+            <Positive as Type<Postgres>>::type_info(),
+            <Decimal as Type<Postgres>>::type_info()
+        );
+        assert_eq!(
+            <Positive as PgHasArrayType>::array_type_info(),
+            <Decimal as PgHasArrayType>::array_type_info()
+        );
+    }
+}
@@ -0,0 +1,360 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 18/10/26
+******************************************************************************/
+
+//! Explicit tolerance-based comparisons for `Positive` values.
+//!
+//! Every comparison operator on `Positive` is exact: `Positive == Positive`
+//! is derived and consistent with `Hash` and `Ord`, and comparisons
+//! against `Decimal` and `f64` compare the exact values in both
+//! directions. Tolerance-based tests are not transitive, so they are never
+//! applied implicitly.
+//!
+//! When a comparison needs a specific tolerance, use [`Tolerance`] with
+//! [`Positive::approx_eq_with`] / [`Positive::approx_cmp`], or wrap values
+//! in [`ToleranceOrd`] to use them as keys of an ordered or hashed map.
+//!
+//! ```rust
+//! use positive::{Positive, Tolerance};
+//! use rust_decimal_macros::dec;
+//!
+//! let a = Positive::new_decimal(dec!(100.00)).unwrap();
+//! let b = Positive::new_decimal(dec!(100.04)).unwrap();
+//!
+//! assert!(a != b);
+//! assert!(a.approx_eq_with(b, Tolerance::Absolute(dec!(0.05))));
+//! assert!(a.approx_eq_with(b, Tolerance::Relative(dec!(0.001))));
+//! assert!(!a.approx_eq_with(b, Tolerance::Ulps(1)));
+//! ```
+
+use crate::constants::EPSILON_CMP;
+use crate::error::{Operation, PositiveError, PositiveResult};
+use crate::positive::Positive;
+use core::cmp::Ordering;
+use core::hash::{Hash, Hasher};
+use num_traits::{FromPrimitive, ToPrimitive};
+use rust_decimal::{Decimal, RoundingStrategy};
+
+/// A tolerance used to decide whether two decimal values are close.
+///
+/// Tolerances are compared by magnitude; a negative tolerance behaves like
+/// its absolute value.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Tolerance {
+    /// Values are close when `|a - b| <= tolerance`.
+    Absolute(Decimal),
+    /// Values are close when `|a - b| <= tolerance * max(|a|, |b|)`.
+    Relative(Decimal),
+    /// Values are close when they differ by at most `n` units in the last
+    /// place of the larger of their two `Decimal` scales
+    /// (`|a - b| <= n * 10^-scale`).
+    Ulps(u32),
+    /// Values are close when either the absolute or the relative test
+    /// passes.
+    Combined {
+        /// Absolute tolerance.
+        absolute: Decimal,
+        /// Relative tolerance.
+        relative: Decimal,
+    },
+}
+
+impl Tolerance {
+    /// Exact comparison: values are close only when they are equal.
+    pub const EXACT: Tolerance = Tolerance::Absolute(Decimal::ZERO);
+
+    /// Tolerance used by [`Positive::is_close`], an absolute
+    /// [`EPSILON_CMP`].
+    pub const DEFAULT: Tolerance = Tolerance::Absolute(EPSILON_CMP);
+
+    /// Returns whether `a` and `b` are within this tolerance of each other.
+    #[must_use]
+    pub fn is_close(&self, a: Decimal, b: Decimal) -> bool {
+        let diff = match a.checked_sub(b) {
+            Some(diff) => diff.abs(),
+            // The difference does not fit in a `Decimal`, so the values
+            // cannot be close under any representable tolerance.
+            None => return false,
+        };
+        match *self {
+            Tolerance::Absolute(absolute) => diff <= absolute.abs(),
+            Tolerance::Relative(relative) => within_relative(diff, a, b, relative),
+            Tolerance::Ulps(n) => {
+                let scale = a.scale().max(b.scale());
+                diff <= Decimal::new(i64::from(n), scale)
+            }
+            Tolerance::Combined { absolute, relative } => {
+                diff <= absolute.abs() || within_relative(diff, a, b, relative)
+            }
+        }
+    }
+
+    /// Compares `a` and `b`, returning `Ordering::Equal` when they are
+    /// within this tolerance and their exact ordering otherwise.
+    #[must_use]
+    pub fn compare(&self, a: Decimal, b: Decimal) -> Ordering {
+        if self.is_close(a, b) {
+            Ordering::Equal
+        } else {
+            a.cmp(&b)
+        }
+    }
+}
+
+impl Default for Tolerance {
+    fn default() -> Self {
+        Tolerance::DEFAULT
+    }
+}
+
+#[inline]
+fn within_relative(diff: Decimal, a: Decimal, b: Decimal, relative: Decimal) -> bool {
+    let largest = a.abs().max(b.abs());
+    match relative.abs().checked_mul(largest) {
+        Some(bound) => diff <= bound,
+        None => true,
+    }
+}
+
+/// Exact `Positive` vs `f64` ordering, shared by both directions.
+///
+/// The `f64` is lifted to `Decimal` and compared exactly; values that
+/// cannot be represented as `Decimal` (NaN, infinities, magnitudes beyond
+/// `Decimal::MAX`) fall back to an `f64` comparison.
+#[inline]
+pub(crate) fn cmp_f64(a: Decimal, b: f64) -> Option<Ordering> {
+    match Decimal::from_f64(b) {
+        Some(b) => Some(a.cmp(&b)),
+        None => a.to_f64().unwrap_or(0.0).partial_cmp(&b),
+    }
+}
+
+impl Positive {
+    /// Returns whether `self` and `other` are within `tolerance`.
+    ///
+    /// `other` may be a `Positive`, a `Decimal` or any integer type that
+    /// converts into `Decimal`.
+    #[inline]
+    #[must_use]
+    pub fn approx_eq_with(&self, other: impl Into<Decimal>, tolerance: Tolerance) -> bool {
+        tolerance.is_close(self.to_dec(), other.into())
+    }
+
+    /// Compares `self` and `other`, returning `Ordering::Equal` when they
+    /// are within `tolerance`.
+    #[inline]
+    #[must_use]
+    pub fn approx_cmp(&self, other: impl Into<Decimal>, tolerance: Tolerance) -> Ordering {
+        tolerance.compare(self.to_dec(), other.into())
+    }
+
+    /// Returns whether `self` and `other` are within [`Tolerance::DEFAULT`].
+    ///
+    /// The comparison operators are exact; use this where values that
+    /// went through different rounding should still compare equal.
+    #[inline]
+    #[must_use]
+    pub fn is_close(&self, other: impl Into<Decimal>) -> bool {
+        Tolerance::DEFAULT.is_close(self.to_dec(), other.into())
+    }
+}
+
+/// A `Positive` that compares, orders and hashes by its value rounded to
+/// the nearest multiple of a quantum.
+///
+/// Pairwise tolerance tests are not transitive, so they cannot back a
+/// lawful `Eq`/`Ord`/`Hash`. `ToleranceOrd` instead snaps every value onto
+/// a grid of width `quantum` (ties rounded to even), which makes it usable
+/// as a key of `BTreeMap`/`HashMap`: two keys are equal exactly when they
+/// snap to the same grid point. Keys built with different quanta should
+/// not be mixed.
+///
+/// ```rust
+/// use positive::{Positive, ToleranceOrd};
+/// use rust_decimal_macros::dec;
+/// use std::collections::BTreeMap;
+///
+/// let tick = Positive::new_decimal(dec!(0.01)).unwrap();
+/// let mut book = BTreeMap::new();
+/// let a = Positive::new_decimal(dec!(100.001)).unwrap();
+/// let b = Positive::new_decimal(dec!(99.999)).unwrap();
+/// book.insert(ToleranceOrd::new(a, tick).unwrap(), 1);
+/// *book.entry(ToleranceOrd::new(b, tick).unwrap()).or_insert(0) += 1;
+/// assert_eq!(book.len(), 1);
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct ToleranceOrd {
+    value: Positive,
+    key: Decimal,
+}
+
+impl ToleranceOrd {
+    /// Wraps `value`, snapping its comparison key to the nearest multiple
+    /// of `quantum`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `ArithmeticError` if `quantum` is zero or the snapped
+    /// key overflows.
+    pub fn new(value: Positive, quantum: Positive) -> PositiveResult<Self> {
+        if quantum.is_zero() {
+            return Err(PositiveError::arithmetic_error(
+                Operation::Quantize,
+                "quantum must be greater than zero",
+            ));
+        }
+        let key = value
+            .to_dec()
+            .checked_div(quantum.to_dec())
+            .map(|steps| steps.round_dp_with_strategy(0, RoundingStrategy::MidpointNearestEven))
+            .and_then(|steps| steps.checked_mul(quantum.to_dec()))
+            .ok_or_else(|| PositiveError::arithmetic_error(Operation::Quantize, "overflow"))?
+            .normalize();
+        Ok(ToleranceOrd { value, key })
+    }
+
+    /// Returns the original, unsnapped value.
+    #[inline]
+    #[must_use]
+    pub fn value(&self) -> Positive {
+        self.value
+    }
+
+    /// Returns the snapped value used for comparisons and hashing.
+    #[inline]
+    #[must_use]
+    pub fn key(&self) -> Decimal {
+        self.key
+    }
+}
+
+impl PartialEq for ToleranceOrd {
+    fn eq(&self, other: &Self) -> bool {
+        self.key == other.key
+    }
+}
+
+impl Eq for ToleranceOrd {}
+
+impl PartialOrd for ToleranceOrd {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for ToleranceOrd {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.key.cmp(&other.key)
+    }
+}
+
+impl Hash for ToleranceOrd {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.key.hash(state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+    #[cfg(feature = "std")]
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_absolute() {
+        let tol = Tolerance::Absolute(dec!(0.01));
+        assert!(tol.is_close(dec!(1.00), dec!(1.01)));
+        assert!(!tol.is_close(dec!(1.00), dec!(1.011)));
+        assert!(Tolerance::EXACT.is_close(dec!(1.0), dec!(1.000)));
+        assert!(!Tolerance::EXACT.is_close(dec!(1.0), dec!(1.0001)));
+    }
+
+    #[test]
+    fn test_relative() {
+        let tol = Tolerance::Relative(dec!(0.01));
+        assert!(tol.is_close(dec!(1000), dec!(1010)));
+        assert!(!tol.is_close(dec!(1000), dec!(1011)));
+        assert!(!tol.is_close(dec!(0), dec!(0.0001)));
+    }
+
+    #[test]
+    fn test_ulps() {
+        assert!(Tolerance::Ulps(2).is_close(dec!(1.00), dec!(1.02)));
+        assert!(!Tolerance::Ulps(2).is_close(dec!(1.00), dec!(1.03)));
+        // The larger scale wins.
+        assert!(!Tolerance::Ulps(2).is_close(dec!(1.0), dec!(1.003)));
+        assert!(Tolerance::Ulps(3).is_close(dec!(1.0), dec!(1.003)));
+    }
+
+    #[test]
+    fn test_combined() {
+        let tol = Tolerance::Combined {
+            absolute: dec!(0.001),
+            relative: dec!(0.01),
+        };
+        assert!(tol.is_close(dec!(0), dec!(0.001)));
+        assert!(tol.is_close(dec!(1000), dec!(1005)));
+        assert!(!tol.is_close(dec!(1), dec!(1.5)));
+    }
+
+    #[test]
+    fn test_compare() {
+        let tol = Tolerance::Absolute(dec!(0.1));
+        assert_eq!(tol.compare(dec!(1), dec!(1.05)), Ordering::Equal);
+        assert_eq!(tol.compare(dec!(1), dec!(1.5)), Ordering::Less);
+        assert_eq!(tol.compare(dec!(2), dec!(1.5)), Ordering::Greater);
+    }
+
+    #[test]
+    fn test_positive_methods() {
+        let a = pos_or_panic!(1.0);
+        let b = Positive::new_decimal(dec!(1.000000000000001)).unwrap();
+        assert_ne!(a, b);
+        assert!(a.is_close(b));
+        assert!(a.approx_eq_with(dec!(1.05), Tolerance::Absolute(dec!(0.1))));
+        assert_eq!(
+            a.approx_cmp(pos_or_panic!(2.0), Tolerance::Absolute(dec!(0.1))),
+            Ordering::Less
+        );
+    }
+
+    #[test]
+    fn test_comparison_operators_are_exact() {
+        let a = pos_or_panic!(1.0);
+        let close = dec!(1.000000000000001);
+        assert!(a != close);
+        assert!(close != a);
+        assert_eq!(a.partial_cmp(&close), Some(Ordering::Less));
+        assert_eq!(close.partial_cmp(&a), Some(Ordering::Greater));
+        assert!(a == dec!(1.00));
+        assert!(a != 1.000000000000001_f64);
+        assert!(1.000000000000001_f64 != a);
+        assert!(a < 1.000000000000001_f64);
+        assert!(a == 1.0_f64);
+        assert_eq!(a.partial_cmp(&f64::NAN), None);
+    }
+
+    #[test]
+    fn test_tolerance_ord() {
+        let tick = pos_or_panic!(0.5);
+        let a = ToleranceOrd::new(pos_or_panic!(10.1), tick).unwrap();
+        let b = ToleranceOrd::new(pos_or_panic!(9.9), tick).unwrap();
+        let c = ToleranceOrd::new(pos_or_panic!(10.4), tick).unwrap();
+        assert_eq!(a, b);
+        assert!(c > a);
+        assert_eq!(a.key(), dec!(10));
+        assert_eq!(a.value(), pos_or_panic!(10.1));
+
+        #[cfg(feature = "std")]
+        {
+            let set: HashSet<ToleranceOrd> = [a, b, c].into_iter().collect();
+            assert_eq!(set.len(), 2);
+        }
+
+        #[cfg(not(feature = "non-zero"))]
+        assert!(ToleranceOrd::new(a.value(), Positive::ZERO).is_err());
+    }
+}
@@ -166,6 +166,21 @@ fn test_positive_decimal_from_string() {
     assert!(Positive::from_str("invalid").is_err());
 }
 
+#[test]
+fn test_positive_from_str_structured_errors() {
+    use std::error::Error as _;
+    let negative = Positive::from_str("-0.0000000000000000001").unwrap_err();
+    assert_eq!(negative.code(), "out_of_bounds");
+    assert!(matches!(
+        negative,
+        positive::PositiveError::OutOfBounds { value, .. } if value == dec!(-0.0000000000000000001)
+    ));
+
+    let invalid = Positive::from_str("invalid").unwrap_err();
+    assert_eq!(invalid.code(), "parse");
+    assert!(invalid.source().is_some());
+}
+
 #[test]
 fn test_positive_decimal_max_min() {
     let a = pos_or_panic!(1.0);
@@ -627,6 +642,18 @@ fn test_round_to_nice_number() {
     assert_eq!(nice4, Positive::TEN);
 }
 
+#[test]
+fn test_round_to_nice_number_other_magnitudes() {
+    assert_eq!(
+        pos_or_panic!(350.0).round_to_nice_number(),
+        pos_or_panic!(500.0)
+    );
+    assert_eq!(
+        pos_or_panic!(0.04).round_to_nice_number(),
+        pos_or_panic!(0.05)
+    );
+}
+
 #[test]
 fn test_sqrt_checked_success() {
     let value = pos_or_panic!(16.0);
@@ -1461,7 +1488,10 @@ fn test_checked_div_f64_zero_is_arithmetic_error() {
     let err = p.checked_div_f64(0.0).unwrap_err();
     assert!(matches!(
         err,
-        positive::PositiveError::ArithmeticError { .. }
+        positive::PositiveError::ArithmeticError {
+            operation: positive::Operation::Div,
+            ..
+        }
     ));
 }
 
@@ -1568,3 +1598,152 @@ fn test_is_multiple_of_dec_zero_divisor() {
     let p = pos_or_panic!(15.0);
     assert!(!p.is_multiple_of_dec(dec!(0)));
 }
+
+#[cfg(not(feature = "non-zero"))]
+#[test]
+fn test_abs_diff() {
+    let a = pos_or_panic!(3.0);
+    let b = pos_or_panic!(5.5);
+    assert_eq!(a.abs_diff(&b), pos_or_panic!(2.5));
+    assert_eq!(b.abs_diff(&a), pos_or_panic!(2.5));
+    assert_eq!(a.abs_diff(&a), Positive::ZERO);
+}
+
+#[test]
+fn test_signed_sub() {
+    use rust_decimal_macros::dec;
+    let a = pos_or_panic!(3.0);
+    let b = pos_or_panic!(5.5);
+    assert_eq!(a.signed_sub(&b), dec!(-2.5));
+    assert_eq!(b.signed_sub(&a), dec!(2.5));
+}
+
+#[test]
+fn test_pct_change() {
+    use rust_decimal_macros::dec;
+    let from = Positive::new_decimal(dec!(100)).unwrap();
+    let up = Positive::new_decimal(dec!(105)).unwrap();
+    let down = Positive::new_decimal(dec!(80)).unwrap();
+    assert_eq!(from.pct_change(&up).unwrap(), dec!(0.05));
+    assert_eq!(from.pct_change(&down).unwrap(), dec!(-0.2));
+}
+
+#[cfg(not(feature = "non-zero"))]
+#[test]
+fn test_pct_change_from_zero() {
+    let err = Positive::ZERO.pct_change(&Positive::ONE).unwrap_err();
+    assert!(matches!(
+        err,
+        positive::PositiveError::ArithmeticError { .. }
+    ));
+}
+
+#[test]
+fn test_pct_change_overflow() {
+    use rust_decimal_macros::dec;
+    let tiny = Positive::new_decimal(dec!(0.0000000000000000000000000001)).unwrap();
+    let err = tiny.pct_change(&Positive::new(1e20).unwrap()).unwrap_err();
+    assert!(matches!(
+        err,
+        positive::PositiveError::ArithmeticError { .. }
+    ));
+}
+
+#[test]
+fn test_relative_diff() {
+    use rust_decimal_macros::dec;
+    let a = Positive::new_decimal(dec!(80)).unwrap();
+    let b = Positive::new_decimal(dec!(100)).unwrap();
+    assert_eq!(a.relative_diff(&b), dec!(0.2));
+    assert_eq!(b.relative_diff(&a), dec!(0.2));
+    assert_eq!(a.relative_diff(&a), Decimal::ZERO);
+}
+
+#[test]
+fn test_log_return() {
+    use rust_decimal_macros::dec;
+    let from = Positive::new_decimal(dec!(100)).unwrap();
+    let to = Positive::new_decimal(dec!(50)).unwrap();
+    let down = from.log_return(&to).unwrap();
+    let up = to.log_return(&from).unwrap();
+    assert!(down < Decimal::ZERO);
+    assert!((down + up).abs() < dec!(1e-20));
+    assert!((up - dec!(0.693147180559945309417232)).abs() < dec!(1e-20));
+    assert_eq!(from.log_return(&from).unwrap(), Decimal::ZERO);
+}
+
+#[cfg(not(feature = "non-zero"))]
+#[test]
+fn test_log_return_zero() {
+    assert!(Positive::ZERO.log_return(&Positive::ONE).is_err());
+    assert!(Positive::ONE.log_return(&Positive::ZERO).is_err());
+}
+
+#[test]
+fn test_is_valid_and_assert_invariant() {
+    let p = pos_or_panic!(2.5);
+    assert!(p.is_valid());
+    p.assert_invariant();
+}
+
+#[cfg(not(feature = "debug-invariants"))]
+#[test]
+fn test_is_valid_detects_broken_ln() {
+    // `ln` of a value below one is negative; without the
+    // `debug-invariants` feature the broken value is only caught by an
+    // explicit check.
+    let broken = pos_or_panic!(0.5).ln();
+    assert!(!broken.is_valid());
+}
+
+#[cfg(not(feature = "debug-invariants"))]
+#[test]
+#[should_panic(expected = "Positive invariant violated")]
+fn test_assert_invariant_panics_on_broken_value() {
+    pos_or_panic!(0.5).ln().assert_invariant();
+}
+
+#[cfg(feature = "debug-invariants")]
+#[test]
+#[should_panic(expected = "Positive invariant violated in ln")]
+fn test_debug_invariants_ln() {
+    let _ = pos_or_panic!(0.5).ln();
+}
+
+#[cfg(feature = "debug-invariants")]
+#[test]
+#[should_panic(expected = "Positive invariant violated in new_unchecked")]
+fn test_debug_invariants_new_unchecked() {
+    use rust_decimal_macros::dec;
+    // SAFETY: deliberately violates the invariant; the `debug-invariants`
+    // feature turns this into a panic before the value escapes.
+    let _ = unsafe { Positive::new_unchecked(dec!(-1)) };
+}
+
+#[cfg(feature = "utoipa")]
+#[test]
+fn test_utoipa_schema_matches_wire_format() {
+    use utoipa::{PartialSchema, ToSchema};
+    assert_eq!(Positive::name(), "Positive");
+    let schema = serde_json::to_value(Positive::schema()).unwrap();
+    assert_eq!(schema["type"], "number");
+    assert!(schema["description"].as_str().unwrap().contains("f64"));
+    assert_eq!(schema["examples"][0], 12.345);
+    #[cfg(not(feature = "non-zero"))]
+    assert_eq!(schema["minimum"], 0);
+    #[cfg(feature = "non-zero")]
+    assert_eq!(schema["exclusiveMinimum"], 0);
+}
+
+#[cfg(feature = "schemars")]
+#[test]
+fn test_schemars_schema_matches_wire_format() {
+    let schema = serde_json::to_value(schemars::schema_for!(Positive)).unwrap();
+    assert_eq!(schema["type"], "number");
+    assert_eq!(schema["title"], "Positive");
+    assert_eq!(schema["examples"][0], 12.345);
+    #[cfg(not(feature = "non-zero"))]
+    assert_eq!(schema["minimum"], 0);
+    #[cfg(feature = "non-zero")]
+    assert_eq!(schema["exclusiveMinimum"], 0);
+}